@@ -1,19 +1,43 @@
-use crate::big_uint::{BigUInt, ParseBigIntError};
+use crate::big_uint::{self, ArithmeticError, BigUInt, ParseBigIntError, ParseErrorKind};
 use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Sign {
     Plus,
     Minus,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+// Zero is always Sign::Plus. Comparison and hashing also treat an unchecked
+// -0 as zero, so they agree with Eq.
+#[derive(Debug, Clone)]
 
 pub struct BigInt {
-    pub sign: Sign,
-    pub magnitude: BigUInt,
+    sign: Sign,
+    magnitude: BigUInt,
+}
+
+impl BigInt {
+    fn effective_sign(&self) -> Sign {
+        if self.magnitude.is_zero() { Sign::Plus } else { self.sign }
+    }
+}
+
+impl PartialEq for BigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BigInt {}
+
+impl Hash for BigInt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.effective_sign().hash(state);
+        self.magnitude.hash(state);
+    }
 }
 
 impl PartialOrd for BigInt {
@@ -24,20 +48,28 @@ impl PartialOrd for BigInt {
 
 impl Ord for BigInt {
     fn cmp(&self, other: &Self) -> Ordering {
-        if self.sign != other.sign {
-            return match self.sign {
+        let (self_sign, other_sign) = (self.effective_sign(), other.effective_sign());
+
+        if self_sign != other_sign {
+            return match self_sign {
                 Sign::Plus => Ordering::Greater,
                 Sign::Minus => Ordering::Less,
             };
         }
 
-        match self.sign {
+        match self_sign {
             Sign::Plus => self.magnitude.cmp(&other.magnitude),
             Sign::Minus => other.magnitude.cmp(&self.magnitude),
         }
     }
 }
 
+impl Default for BigInt {
+    fn default() -> Self {
+        BigInt::new()
+    }
+}
+
 impl BigInt {
     pub fn new() -> Self {
         BigInt { sign: Sign::Plus, magnitude: BigUInt::new() }
@@ -54,11 +86,197 @@ impl BigInt {
         BigInt { sign, magnitude }.normalize()
     }   
 
+    pub(crate) fn from_u128(value: u128) -> Self {
+        BigInt { sign: Sign::Plus, magnitude: BigUInt::from_u128(value) }.normalize()
+    }
+
+    pub(crate) fn from_i128(value: i128) -> Self {
+        let sign = if value < 0 { Sign::Minus } else { Sign::Plus };
+        let magnitude = BigUInt::from_u128(value.unsigned_abs());
+
+        BigInt { sign, magnitude }.normalize()
+    }
+
+    pub fn from_sign_magnitude(sign: Sign, mut magnitude: BigUInt) -> Self {
+        magnitude.truncate_assign();
+        BigInt { sign, magnitude }.normalize()
+    }
+
+    // Skips normalization, so a zero magnitude keeps the given sign
+    pub fn from_sign_magnitude_unchecked(sign: Sign, magnitude: BigUInt) -> Self {
+        BigInt { sign, magnitude }
+    }
+
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    pub fn magnitude(&self) -> &BigUInt {
+        &self.magnitude
+    }
+
+    pub fn into_parts(self) -> (Sign, BigUInt) {
+        (self.sign, self.magnitude)
+    }
 
+    pub fn shl(&self, bits: usize) -> Self {
+        BigInt { sign: self.sign, magnitude: self.magnitude.shl(bits) }.normalize()
+    }
+
+    // Rounds toward negative infinity like `>>` on primitive signed integers,
+    // so a negative value never shifts past -1
+    pub fn shr(&self, bits: usize) -> Self {
+        match self.effective_sign() {
+            Sign::Plus => BigInt { sign: Sign::Plus, magnitude: self.magnitude.shr(bits) },
+            Sign::Minus => {
+                let one = BigUInt::from_u32(1);
+                let magnitude = self.magnitude.sub(&one).shr(bits).add(&one);
+                BigInt { sign: Sign::Minus, magnitude }
+            }
+        }
+    }
+
+    pub fn bitand(&self, other: &Self) -> Self {
+        self.bitwise(other, |a, b| a & b)
+    }
+
+    pub fn bitor(&self, other: &Self) -> Self {
+        self.bitwise(other, |a, b| a | b)
+    }
+
+    pub fn bitxor(&self, other: &Self) -> Self {
+        self.bitwise(other, |a, b| a ^ b)
+    }
+
+    // !x == -x - 1 in two's complement
+    pub fn not(&self) -> Self {
+        self.negate().sub(&BigInt::from_u32(1))
+    }
+
+    // Bit inspection follows the infinite two's complement form as well. The
+    // bit length excludes the sign, so -1 has length 0 and -2^n has length n.
+    pub fn bits(&self) -> usize {
+        match self.effective_sign() {
+            Sign::Plus => self.magnitude.bits(),
+            Sign::Minus => self.magnitude.sub(&BigUInt::from_u32(1)).bits(),
+        }
+    }
+
+    // Negative values have infinitely many ones, so only None is honest there
+    pub fn count_ones(&self) -> Option<usize> {
+        match self.effective_sign() {
+            Sign::Plus => Some(self.magnitude.count_ones()),
+            Sign::Minus => None,
+        }
+    }
+
+    // Zeros of a negative value are the ones of !x; non-negative values have
+    // infinitely many
+    pub fn count_zeros(&self) -> Option<usize> {
+        match self.effective_sign() {
+            Sign::Plus => None,
+            Sign::Minus => Some(self.magnitude.sub(&BigUInt::from_u32(1)).count_ones()),
+        }
+    }
+
+    // Negation keeps the trailing zeros, so this is the magnitude's count
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        self.magnitude.trailing_zeros()
+    }
+
+    // Zero bits above the top set bit within its limb, 32 for zero. Negative
+    // values have ones all the way up, so there is nothing to count
+    pub fn leading_zeros(&self) -> Option<u32> {
+        match self.effective_sign() {
+            Sign::Plus => Some(self.magnitude.leading_zeros()),
+            Sign::Minus => None,
+        }
+    }
+
+    // -m matches m up to and including its lowest set bit and is inverted
+    // above it, so no |x| - 1 has to be built
+    pub fn bit(&self, i: usize) -> bool {
+        match self.magnitude.trailing_zeros() {
+            Some(zeros) if self.sign == Sign::Minus && i > zeros => !self.magnitude.bit(i),
+            _ => self.magnitude.bit(i),
+        }
+    }
+
+    pub fn set_bit(&mut self, i: usize, value: bool) {
+        if self.bit(i) == value {
+            return;
+        }
+
+        let mask = BigInt::from_u32(1).shl(i);
+        *self = self.bitxor(&mask);
+    }
+
+    // Negative values are never powers of two
+    pub fn is_power_of_two(&self) -> bool {
+        self.effective_sign() == Sign::Plus && self.magnitude.is_power_of_two()
+    }
+
+    // Smallest power of two not below self, so 1 for zero and every negative
+    pub fn next_power_of_two(&self) -> Self {
+        match self.effective_sign() {
+            Sign::Plus => BigInt { sign: Sign::Plus, magnitude: self.magnitude.next_power_of_two() },
+            Sign::Minus => BigInt::from_u32(1),
+        }
+    }
+
+    // Positions of the set bits in increasing order, None for negative values
+    // whose ones never end
+    pub fn iter_ones(&self) -> Option<impl Iterator<Item = usize> + '_> {
+        match self.effective_sign() {
+            Sign::Plus => Some(self.magnitude.iter_ones()),
+            Sign::Minus => None,
+        }
+    }
+
+    // Bitwise ops act on the infinite two's complement form, like Python
+    // integers: a negative x is !(|x| - 1) followed by infinitely many ones
+    fn bitwise(&self, other: &Self, op: impl Fn(u32, u32) -> u32) -> Self {
+        let len = self.magnitude.as_limbs().len().max(other.magnitude.as_limbs().len());
+        let a = self.twos_complement(len);
+        let b = other.twos_complement(len);
+
+        let negative = op(self.sign_fill(), other.sign_fill()) != 0;
+        let limbs: Vec<u32> = a.iter().zip(&b).map(|(&x, &y)| op(x, y)).collect();
+
+        if !negative {
+            return BigInt { sign: Sign::Plus, magnitude: BigUInt::from_limbs_le(limbs) };
+        }
+
+        let inverted = limbs.iter().map(|&l| !l).collect();
+        let magnitude = BigUInt::from_limbs_le(inverted).add(&BigUInt::from_u32(1));
+        BigInt { sign: Sign::Minus, magnitude }
+    }
+
+    fn sign_fill(&self) -> u32 {
+        match self.effective_sign() {
+            Sign::Plus => 0,
+            Sign::Minus => u32::MAX,
+        }
+    }
+
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        match self.effective_sign() {
+            Sign::Plus => {
+                let mut limbs = self.magnitude.as_limbs().to_vec();
+                limbs.resize(len, 0);
+                limbs
+            }
+            Sign::Minus => {
+                let mut limbs = self.magnitude.sub(&BigUInt::from_u32(1)).into_limbs();
+                limbs.resize(len, 0);
+                limbs.iter().map(|&l| !l).collect()
+            }
+        }
+    }
 
     pub fn negate(&self) -> Self {
         if self.magnitude.is_zero() {
-            return self.clone();
+            return BigInt::new();
         }
 
         let new_sign = match self.sign {
@@ -108,8 +326,82 @@ impl BigInt {
         BigInt { sign: new_sign, magnitude: new_magnitude }.normalize()
     }
 
-    pub fn div(&self, other: &Self) -> (Self, Self) {
-        let (quotient_magnitude, remainder_magnitude) = self.magnitude.div(&other.magnitude);
+    pub fn pow(&self, exp: u32) -> Self {
+        self.try_pow(exp).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_pow(&self, exp: u32) -> Result<Self, ArithmeticError> {
+        let magnitude = self.magnitude.try_pow(exp)?;
+        let sign = if exp % 2 == 1 { self.sign } else { Sign::Plus };
+
+        Ok(BigInt { sign, magnitude }.normalize())
+    }
+
+    pub fn checked_pow(&self, exp: u32) -> Option<Self> {
+        self.try_pow(exp).ok()
+    }
+
+    // Remainder in [0, |m|), unlike the remainder of div_rem which takes the
+    // dividend's sign
+    pub fn rem_euclid(&self, m: &Self) -> Self {
+        let r = self.magnitude.div_rem(&m.magnitude).1;
+
+        if self.effective_sign() == Sign::Minus && !r.is_zero() {
+            BigInt { sign: Sign::Plus, magnitude: m.magnitude.sub(&r) }
+        } else {
+            BigInt { sign: Sign::Plus, magnitude: r }
+        }
+    }
+
+    // Remainder with the sign of m, like Python's %
+    pub fn mod_floor(&self, m: &Self) -> Self {
+        let r = self.rem_euclid(m);
+
+        if m.effective_sign() == Sign::Minus && !r.magnitude.is_zero() {
+            r.add(m)
+        } else {
+            r
+        }
+    }
+
+    // Always non-negative, gcd(0, 0) = 0
+    pub fn gcd(&self, other: &Self) -> Self {
+        BigInt::from_sign_magnitude(Sign::Plus, self.magnitude.gcd(&other.magnitude))
+    }
+
+    pub fn lcm(&self, other: &Self) -> Self {
+        BigInt::from_sign_magnitude(Sign::Plus, self.magnitude.lcm(&other.magnitude))
+    }
+
+    // (g, x, y) with self * x + other * y = g and g non-negative
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let (g, x, y) = self.magnitude.extended_gcd(&other.magnitude);
+        let x = if self.effective_sign() == Sign::Minus { x.negate() } else { x };
+        let y = if other.effective_sign() == Sign::Minus { y.negate() } else { y };
+
+        (BigInt::from_sign_magnitude(Sign::Plus, g), x, y)
+    }
+
+    // Result in [0, |modulus|). A negative exponent raises the modular inverse
+    // of the base, which panics if the base isn't invertible.
+    pub fn modpow(&self, exp: &BigInt, modulus: &BigInt) -> BigInt {
+        let m = &modulus.magnitude;
+        if m.is_zero() {
+            panic!("Division by zero");
+        }
+
+        let mut base = self.rem_euclid(modulus).magnitude;
+        if exp.effective_sign() == Sign::Minus {
+            base = base.mod_inverse(m).expect("Modular inverse does not exist");
+        }
+
+        BigInt::from_sign_magnitude(Sign::Plus, base.modpow(&exp.magnitude, m))
+    }
+
+    // Truncating division like `/` and `%` on primitives: the quotient rounds
+    // toward zero and the remainder takes the dividend's sign
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let (quotient_magnitude, remainder_magnitude) = self.magnitude.div_rem(&other.magnitude);
         let quotient_sign = if self.sign == other.sign {
             Sign::Plus
         } else {
@@ -119,38 +411,183 @@ impl BigInt {
         (BigInt { sign: quotient_sign, magnitude: quotient_magnitude }.normalize(),
          BigInt { sign: self.sign, magnitude: remainder_magnitude }.normalize())
     }
-}
 
-impl FromStr for BigInt {
-    type Err = ParseBigIntError;
+    // The old name, which std::ops::Div shadows once the trait is in scope
+    #[deprecated(note = "use div_rem, `div` resolves to std::ops::Div when the trait is in scope")]
+    pub fn div(&self, other: &Self) -> (Self, Self) {
+        self.div_rem(other)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    // Truncating like div_rem, without the zero divisor panic
+    pub fn try_div(&self, other: &Self) -> Result<(Self, Self), ArithmeticError> {
+        if other.magnitude.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(self.div_rem(other))
+    }
+
+    pub fn try_rem(&self, other: &Self) -> Result<Self, ArithmeticError> {
+        self.try_div(other).map(|(_, r)| r)
+    }
+
+    // The quotient only, like checked_div on primitives
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        self.try_div(other).ok().map(|(q, _)| q)
+    }
+
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        self.try_rem(other).ok()
+    }
+
+    // Quotient rounded toward negative infinity, the remainder takes the
+    // divisor's sign: 7 / -2 = (-4, -1) and -7 / 2 = (-4, 1)
+    pub fn div_rem_floor(&self, other: &Self) -> (Self, Self) {
+        let (q, r) = self.div_rem(other);
+
+        if !r.magnitude.is_zero() && r.sign != other.sign {
+            (q.sub(&BigInt::from_u32(1)), r.add(other))
+        } else {
+            (q, r)
+        }
+    }
+
+    pub fn div_floor(&self, other: &Self) -> Self {
+        self.div_rem_floor(other).0
+    }
+
+    // Quotient rounded toward positive infinity: 7 / 2 = 4 and -7 / 2 = -3
+    pub fn div_ceil(&self, other: &Self) -> Self {
+        let (q, r) = self.div_rem(other);
+
+        if !r.magnitude.is_zero() && r.sign == other.sign {
+            q.add(&BigInt::from_u32(1))
+        } else {
+            q
+        }
+    }
+
+    // Quotient matching rem_euclid, so self = q * other + r with 0 <= r < |other|
+    pub fn div_rem_euclid(&self, other: &Self) -> (Self, Self) {
+        let (q, r) = self.div_rem(other);
+
+        if r.sign == Sign::Minus && !r.magnitude.is_zero() {
+            match other.sign {
+                Sign::Plus => (q.sub(&BigInt::from_u32(1)), r.add(other)),
+                Sign::Minus => (q.add(&BigInt::from_u32(1)), r.sub(other)),
+            }
+        } else {
+            (q, r)
+        }
+    }
+
+    pub fn div_euclid(&self, other: &Self) -> Self {
+        self.div_rem_euclid(other).0
+    }
+
+    // Quotient rounded to the nearest integer, ties to the even neighbour:
+    // 5 / 2 = 2, 7 / 2 = 4 and -5 / 2 = -2
+    pub fn div_round(&self, other: &Self) -> Self {
+        let (q, r) = self.div_rem(other);
+        if r.magnitude.is_zero() {
+            return q;
+        }
+
+        let twice_r = r.magnitude.shl(1);
+        let round_away = match twice_r.cmp(&other.magnitude) {
+            Ordering::Greater => true,
+            Ordering::Equal => q.magnitude.bit(0),
+            Ordering::Less => false,
+        };
+
+        if !round_away {
+            return q;
+        }
+
+        // the exact quotient is negative when the operand signs differ
+        if self.sign == other.sign {
+            q.add(&BigInt::from_u32(1))
+        } else {
+            q.sub(&BigInt::from_u32(1))
+        }
+    }
+
+    // An optional leading + or - followed by case-insensitive digits
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseBigIntError::new(ParseErrorKind::InvalidRadix, 0, None));
+        }
         if s.is_empty() {
-            return Err(ParseBigIntError);
+            return Err(ParseBigIntError::new(ParseErrorKind::Empty, 0, None));
         }
 
-        let (sign, magnitude_str) = if s.starts_with('-') {
-            (Sign::Minus, &s[1..])
-        } else if s.starts_with('+') {
-            (Sign::Plus, &s[1..])
+        let (sign, magnitude_str) = if let Some(rest) = s.strip_prefix('-') {
+            (Sign::Minus, rest)
+        } else if let Some(rest) = s.strip_prefix('+') {
+            (Sign::Plus, rest)
         } else {
             (Sign::Plus, s)
         };
 
         if magnitude_str.is_empty() {
-            return Err(ParseBigIntError);
+            return Err(ParseBigIntError::new(ParseErrorKind::SignOnly, s.len(), None));
         }
 
-        let magnitude = BigUInt::from_str(magnitude_str)?;
+        let magnitude = big_uint::parse_digits(magnitude_str, radix, s.len() - magnitude_str.len())?;
         Ok(BigInt { sign, magnitude }.normalize())
     }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.magnitude.to_str_radix(radix);
+        if self.sign == Sign::Minus && !self.magnitude.is_zero() {
+            format!("-{}", digits)
+        } else {
+            digits
+        }
+    }
 }
 
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigInt::from_str_radix(s, 10)
+    }
+}
+
+// Signed output is the magnitude behind a minus sign, so {:x} of -255 is
+// -ff and {:#x} is -0xff rather than a two's complement bit pattern
 impl fmt::Display for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.sign == Sign::Minus && !self.magnitude.is_zero() {
-            write!(f, "-")?;
-        }
-        write!(f, "{}", self.magnitude)
+        big_uint::fmt_radix(f, self.effective_sign() == Sign::Plus, &self.magnitude, 10, "")
+    }
+}
+
+impl fmt::LowerHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        big_uint::fmt_radix(f, self.effective_sign() == Sign::Plus, &self.magnitude, 16, "0x")
+    }
+}
+
+impl fmt::UpperHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        big_uint::fmt_upper_hex(f, self.effective_sign() == Sign::Plus, &self.magnitude)
+    }
+}
+
+impl fmt::Octal for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        big_uint::fmt_radix(f, self.effective_sign() == Sign::Plus, &self.magnitude, 8, "0o")
+    }
+}
+
+impl fmt::Binary for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        big_uint::fmt_radix(f, self.effective_sign() == Sign::Plus, &self.magnitude, 2, "0b")
+    }
+}
+
+impl fmt::LowerExp for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        big_uint::fmt_exp(f, self.effective_sign() == Sign::Plus, &self.magnitude)
     }
 }
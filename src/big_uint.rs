@@ -1,20 +1,241 @@
+use crate::big_int::{BigInt, Sign};
+use crate::gcd;
+use crate::modular;
+use crate::ntt;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::str::FromStr;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+const DEFAULT_KARATSUBA_THRESHOLD: usize = 32;
+const DEFAULT_TOOM3_THRESHOLD: usize = 300;
+const DEFAULT_TOOM4_THRESHOLD: usize = 1000;
+const DEFAULT_NTT_THRESHOLD: usize = 2000;
+const DEFAULT_BURNIKEL_ZIEGLER_THRESHOLD: usize = 100;
+const DEFAULT_BURNIKEL_ZIEGLER_OFFSET: usize = 40;
+const DEFAULT_POW_LIMB_LIMIT: usize = if usize::BITS > 32 { 1 << 31 } else { usize::MAX / 32 };
+const DEFAULT_RADIX_POWER_CACHE_LIMIT: usize = 1 << 14;
+
+static KARATSUBA_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_KARATSUBA_THRESHOLD);
+static TOOM3_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_TOOM3_THRESHOLD);
+static TOOM4_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_TOOM4_THRESHOLD);
+static NTT_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_NTT_THRESHOLD);
+static BURNIKEL_ZIEGLER_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_BURNIKEL_ZIEGLER_THRESHOLD);
+static BURNIKEL_ZIEGLER_OFFSET: AtomicUsize = AtomicUsize::new(DEFAULT_BURNIKEL_ZIEGLER_OFFSET);
+static POW_LIMB_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_POW_LIMB_LIMIT);
+static RADIX_POWER_CACHE_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_RADIX_POWER_CACHE_LIMIT);
+
+// Operands with at least this many limbs (in the shorter factor) are multiplied with Karatsuba
+pub fn karatsuba_threshold() -> usize {
+    KARATSUBA_THRESHOLD.load(AtomicOrdering::Relaxed)
+}
+
+// Values below 2 are clamped, Karatsuba needs at least two limbs to split
+pub fn set_karatsuba_threshold(limbs: usize) {
+    KARATSUBA_THRESHOLD.store(limbs.max(2), AtomicOrdering::Relaxed);
+}
+
+// Balanced operands with at least this many limbs are multiplied with Toom-3
+pub fn toom3_threshold() -> usize {
+    TOOM3_THRESHOLD.load(AtomicOrdering::Relaxed)
+}
+
+pub fn set_toom3_threshold(limbs: usize) {
+    TOOM3_THRESHOLD.store(limbs.max(3), AtomicOrdering::Relaxed);
+}
+
+// Balanced operands with at least this many limbs are multiplied with Toom-4
+pub fn toom4_threshold() -> usize {
+    TOOM4_THRESHOLD.load(AtomicOrdering::Relaxed)
+}
+
+pub fn set_toom4_threshold(limbs: usize) {
+    TOOM4_THRESHOLD.store(limbs.max(4), AtomicOrdering::Relaxed);
+}
+
+// Operands with at least this many limbs (in the shorter factor) are multiplied
+// with the three-prime NTT, as long as the product fits a single transform
+pub fn ntt_threshold() -> usize {
+    NTT_THRESHOLD.load(AtomicOrdering::Relaxed)
+}
+
+pub fn set_ntt_threshold(limbs: usize) {
+    NTT_THRESHOLD.store(limbs.max(1), AtomicOrdering::Relaxed);
+}
+
+// Divisors with at least this many limbs are divided with Burnikel-Ziegler
+// recursion, smaller ones with schoolbook (Knuth) division
+pub fn burnikel_ziegler_threshold() -> usize {
+    BURNIKEL_ZIEGLER_THRESHOLD.load(AtomicOrdering::Relaxed)
+}
+
+// Values below 2 are clamped, the recursion needs a divisor it can halve
+pub fn set_burnikel_ziegler_threshold(limbs: usize) {
+    BURNIKEL_ZIEGLER_THRESHOLD.store(limbs.max(2), AtomicOrdering::Relaxed);
+}
+
+// Burnikel-Ziegler is only used when the dividend is at least this many limbs
+// longer than the divisor, short quotients are cheaper with schoolbook division
+pub fn burnikel_ziegler_offset() -> usize {
+    BURNIKEL_ZIEGLER_OFFSET.load(AtomicOrdering::Relaxed)
+}
+
+pub fn set_burnikel_ziegler_offset(limbs: usize) {
+    BURNIKEL_ZIEGLER_OFFSET.store(limbs, AtomicOrdering::Relaxed);
+}
+
+// Powers whose result would need more limbs than this are refused with
+// ArithmeticError::ResourceLimit before anything is allocated
+pub fn pow_limb_limit() -> usize {
+    POW_LIMB_LIMIT.load(AtomicOrdering::Relaxed)
+}
+
+// Clamped so the limit in bits still fits a usize
+pub fn set_pow_limb_limit(limbs: usize) {
+    POW_LIMB_LIMIT.store(limbs.min(usize::MAX / 32), AtomicOrdering::Relaxed);
+}
+
+// Radix conversion powers longer than this are still computed, but dropped
+// when the conversion ends instead of staying in the per-thread cache
+pub fn radix_power_cache_limit() -> usize {
+    RADIX_POWER_CACHE_LIMIT.load(AtomicOrdering::Relaxed)
+}
+
+pub fn set_radix_power_cache_limit(limbs: usize) {
+    RADIX_POWER_CACHE_LIMIT.store(limbs, AtomicOrdering::Relaxed);
+}
+
+// Frees the radix conversion powers cached by the calling thread
+pub fn clear_radix_power_cache() {
+    RADIX_POWERS.with(|cache| cache.borrow_mut().clear());
+}
+
+// Why a string failed to parse, see ParseBigIntError::kind
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseErrorKind {
+    Empty,
+    // A character that isn't a digit of the radix, including a second sign
+    InvalidDigit,
+    // The radix is outside 2..=36
+    InvalidRadix,
+    // A sign with no digits after it
+    SignOnly,
+    // The value would have more bits than fit in a usize
+    TooLarge,
+}
+
+// Position is the byte offset into the whole input, sign included. It points
+// at the offending character for InvalidDigit and at the end of the input for
+// SignOnly, the other kinds report 0.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseBigIntError {
+    kind: ParseErrorKind,
+    position: usize,
+    character: Option<char>,
+}
+
+impl ParseBigIntError {
+    pub(crate) fn new(kind: ParseErrorKind, position: usize, character: Option<char>) -> Self {
+        ParseBigIntError { kind, position, character }
+    }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseBigIntError;
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    // The offending character, only set for InvalidDigit
+    pub fn character(&self) -> Option<char> {
+        self.character
+    }
+}
 
 impl fmt::Display for ParseBigIntError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Invalid digit in string")
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "Cannot parse a number from an empty string"),
+            ParseErrorKind::InvalidDigit => match self.character {
+                Some(c) => write!(f, "Invalid digit {:?} at position {}", c, self.position),
+                None => write!(f, "Invalid digit at position {}", self.position),
+            },
+            ParseErrorKind::InvalidRadix => write!(f, "Radix must be between 2 and 36"),
+            ParseErrorKind::SignOnly => write!(f, "Sign without digits at position {}", self.position),
+            ParseErrorKind::TooLarge => write!(f, "Number too large to parse"),
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl std::error::Error for ParseBigIntError {}
+
+// Returned by the TryFrom conversions into primitive integers
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TryFromBigIntError {
+    // The value doesn't fit into the target type
+    Overflow,
+    // A negative value was converted into an unsigned type
+    Negative,
+}
+
+impl fmt::Display for TryFromBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TryFromBigIntError::Overflow => write!(f, "Value out of range for the target integer type"),
+            TryFromBigIntError::Negative => write!(f, "Negative value converted to an unsigned integer type"),
+        }
+    }
+}
+
+impl std::error::Error for TryFromBigIntError {}
+
+// Returned by the try_* methods, the messages match the panics of the plain
+// methods. The checked_* methods return None in the same cases
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ArithmeticError {
+    // An unsigned subtraction would go below zero
+    Underflow,
+    DivisionByZero,
+    // The result would be too large to allocate, see pow_limb_limit
+    ResourceLimit,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArithmeticError::Underflow => write!(f, "Subtraction underflow"),
+            ArithmeticError::DivisionByZero => write!(f, "Division by zero"),
+            ArithmeticError::ResourceLimit => write!(f, "Result too large to allocate"),
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+// Canonical form: no trailing zero limbs and at least one limb, so zero is [0].
+// Every operation returns canonical values, and equality, ordering and hashing
+// only look at the significant limbs, so padded values still behave as keys.
+#[derive(Debug, Clone)]
 
 pub struct BigUInt {
-    pub limbs: Vec<u32>,
+    limbs: Vec<u32>,
+}
+
+impl PartialEq for BigUInt {
+    fn eq(&self, other: &Self) -> bool {
+        trim(&self.limbs) == trim(&other.limbs)
+    }
+}
+
+impl Eq for BigUInt {}
+
+impl Hash for BigUInt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        trim(&self.limbs).hash(state);
+    }
 }
 
 impl PartialOrd for BigUInt {
@@ -25,24 +246,13 @@ impl PartialOrd for BigUInt {
 
 impl Ord for BigUInt {
     fn cmp(&self, other: &Self) -> Ordering {
-        let self_real_len = self.limbs.iter().rposition(|&l| l != 0).map_or(0, |p| p + 1).max(1);
-        let other_real_len = other.limbs.iter().rposition(|&l| l != 0).map_or(0, |p| p + 1).max(1);
-
-        let len_cmp = self_real_len.cmp(&other_real_len);
-        if len_cmp != Ordering::Equal {
-            return len_cmp;
-        }
-
-        for i in (0..self_real_len).rev() {
-            let self_val = self.limbs.get(i).unwrap_or(&0);
-            let other_val = other.limbs.get(i).unwrap_or(&0);
-            let limb_cmp = self_val.cmp(other_val);
-            if limb_cmp != Ordering::Equal {
-                return limb_cmp;
-            }
-        }
+        cmp_slices(&self.limbs, &other.limbs)
+    }
+}
 
-        Ordering::Equal
+impl Default for BigUInt {
+    fn default() -> Self {
+        BigUInt::new()
     }
 }
 
@@ -59,217 +269,1565 @@ impl BigUInt {
         BigUInt { limbs: vec![_n] }
     }
 
+    pub(crate) fn from_u128(n: u128) -> Self {
+        BigUInt::from_limbs_le(vec![n as u32, (n >> 32) as u32, (n >> 64) as u32, (n >> 96) as u32])
+    }
+
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        let limbs = trim(&self.limbs);
+        if limbs.len() > 4 {
+            return None;
+        }
+
+        Some(limbs.iter().rev().fold(0, |acc, &limb| (acc << 32) | limb as u128))
+    }
+
+    // Little-endian limbs, trimmed down to at least one limb
+    pub fn from_limbs_le(mut limbs: Vec<u32>) -> Self {
+        let len = trim(&limbs).len().max(1);
+        limbs.resize(len, 0);
+        BigUInt { limbs }
+    }
+
+    // Skips the trimming. Padded or empty limbs are still handled by every
+    // operation, but as_limbs and Debug show them as given.
+    pub fn from_limbs_le_unchecked(limbs: Vec<u32>) -> Self {
+        BigUInt { limbs }
+    }
+
+    pub fn as_limbs(&self) -> &[u32] {
+        &self.limbs
+    }
+
+    pub fn into_limbs(self) -> Vec<u32> {
+        self.limbs
+    }
+
     pub fn truncate(&self) -> Self {
-        let mut result = self.limbs.clone();
+        let mut result = self.clone();
+        result.truncate_assign();
+        result
+    }
 
-        while result.len() > 1 && result.last() == Some(&0) {
-            result.pop();
+    pub fn truncate_assign(&mut self) {
+        while self.limbs.len() > 1 && self.limbs.last() == Some(&0) {
+            self.limbs.pop();
         }
 
-        BigUInt { limbs: result }
+        if self.limbs.is_empty() {
+            self.limbs.push(0);
+        }
     }
 
     pub fn shift_limbs(&self, n: usize) -> Self {
+        let mut result = self.clone();
+        result.shl_limbs_assign(n);
+        result
+    }
+
+    pub fn shl_limbs_assign(&mut self, n: usize) {
+        self.truncate_assign();
+        if self.is_zero() {
+            return;
+        }
+
+        self.limbs.splice(0..0, std::iter::repeat_n(0, n));
+    }
+
+    pub fn bitand(&self, _other: &Self) -> Self {
+        let mut result = self.clone();
+        result.bitand_assign(_other);
+        result
+    }
+
+    pub fn bitand_assign(&mut self, _other: &Self) {
+        self.limbs.truncate(_other.limbs.len());
+        for (a, &b) in self.limbs.iter_mut().zip(&_other.limbs) {
+            *a &= b;
+        }
+        self.truncate_assign();
+    }
+
+    pub fn bitor(&self, _other: &Self) -> Self {
+        let mut result = self.clone();
+        result.bitor_assign(_other);
+        result
+    }
+
+    pub fn bitor_assign(&mut self, _other: &Self) {
+        if self.limbs.len() < _other.limbs.len() {
+            self.limbs.resize(_other.limbs.len(), 0);
+        }
+        for (a, &b) in self.limbs.iter_mut().zip(&_other.limbs) {
+            *a |= b;
+        }
+        self.truncate_assign();
+    }
+
+    pub fn bitxor(&self, _other: &Self) -> Self {
+        let mut result = self.clone();
+        result.bitxor_assign(_other);
+        result
+    }
+
+    pub fn bitxor_assign(&mut self, _other: &Self) {
+        if self.limbs.len() < _other.limbs.len() {
+            self.limbs.resize(_other.limbs.len(), 0);
+        }
+        for (a, &b) in self.limbs.iter_mut().zip(&_other.limbs) {
+            *a ^= b;
+        }
+        self.truncate_assign();
+    }
+
+    pub fn bits(&self) -> usize {
+        bit_length(&self.limbs)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.limbs.iter().map(|l| l.count_ones() as usize).sum()
+    }
+
+    // Zero bits below the bit length, so zero has none
+    pub fn count_zeros(&self) -> usize {
+        self.bits() - self.count_ones()
+    }
+
+    // None for zero, which has no set bit to stop at
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        let i = self.limbs.iter().position(|&l| l != 0)?;
+        Some(i * 32 + self.limbs[i].trailing_zeros() as usize)
+    }
+
+    // Zero bits above the top set bit within its limb, 32 for zero
+    pub fn leading_zeros(&self) -> u32 {
+        trim(&self.limbs).last().map_or(32, |l| l.leading_zeros())
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs.get(i / 32).is_some_and(|&l| l >> (i % 32) & 1 == 1)
+    }
+
+    pub fn set_bit(&mut self, i: usize, value: bool) {
+        let (index, mask) = (i / 32, 1 << (i % 32));
+
+        if value {
+            if self.limbs.len() <= index {
+                self.limbs.resize(index + 1, 0);
+            }
+            self.limbs[index] |= mask;
+        } else if let Some(limb) = self.limbs.get_mut(index) {
+            *limb &= !mask;
+            self.truncate_assign();
+        }
+    }
+
+    pub fn is_power_of_two(&self) -> bool {
+        self.count_ones() == 1
+    }
+
+    // Smallest power of two not below self, 1 for zero like the primitives
+    pub fn next_power_of_two(&self) -> Self {
         if self.is_zero() {
-            return self.clone();
+            return BigUInt::from_u32(1);
+        }
+        if self.is_power_of_two() {
+            return self.truncate();
+        }
+
+        BigUInt::from_u32(1).shl(self.bits())
+    }
+
+    // Positions of the set bits in increasing order
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.limbs.iter().enumerate().flat_map(|(i, &limb)| {
+            let mut rest = limb;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(i * 32 + bit)
+            })
+        })
+    }
+
+    pub fn shl(&self, bits: usize) -> Self {
+        let mut result = self.clone();
+        result.shl_assign(bits);
+        result
+    }
+
+    pub fn shl_assign(&mut self, bits: usize) {
+        let shift = (bits % 32) as u32;
+
+        if shift > 0 {
+            let mut carry = 0;
+            for limb in self.limbs.iter_mut() {
+                let next = *limb >> (32 - shift);
+                *limb = (*limb << shift) | carry;
+                carry = next;
+            }
+            self.limbs.push(carry);
+        }
+
+        self.shl_limbs_assign(bits / 32);
+    }
+
+    pub fn shr(&self, bits: usize) -> Self {
+        let mut result = self.clone();
+        result.shr_assign(bits);
+        result
+    }
+
+    pub fn shr_assign(&mut self, bits: usize) {
+        let whole = bits / 32;
+        if whole >= self.limbs.len() {
+            self.limbs.clear();
+            self.limbs.push(0);
+            return;
+        }
+
+        self.limbs.drain(..whole);
+
+        let shift = (bits % 32) as u32;
+        if shift > 0 {
+            let mut carry = 0;
+            for limb in self.limbs.iter_mut().rev() {
+                let next = *limb << (32 - shift);
+                *limb = (*limb >> shift) | carry;
+                carry = next;
+            }
         }
 
-        let mut new_limbs = vec![0; n];
-        new_limbs.extend(&self.limbs);
-        
-        BigUInt { limbs: new_limbs }
+        self.truncate_assign();
     }
 
     pub fn add(&self, _other: &Self) -> Self {
-        let max_len = self.limbs.len().max(_other.limbs.len());
-        let mut result = vec![];
-        let mut carry: u32 = 0;
+        let mut result = self.clone();
+        result.add_assign(_other);
+        result
+    }
 
-        for i in 0..max_len {
-            let sum = (*self.limbs.get(i).unwrap_or(&0) as u64) + (*_other.limbs.get(i).unwrap_or(&0) as u64) + (carry as u64);
-            carry = (sum >> 32) as u32;
-            result.push(sum as u32);
+    pub fn add_assign(&mut self, _other: &Self) {
+        let other_len = trim(&_other.limbs).len();
+        if self.limbs.len() < other_len {
+            self.limbs.resize(other_len, 0);
         }
 
+        let carry = add_into(&mut self.limbs, &_other.limbs);
         if carry > 0 {
-            result.push(carry);
+            self.limbs.push(carry);
         }
 
-        BigUInt { limbs: result }.truncate()
+        self.truncate_assign();
     }
 
     // BigUInt only handles subtraction when a >= b
     pub fn sub(&self, _other: &Self) -> Self {
-        if self < _other {
+        let mut result = self.clone();
+        result.sub_assign(_other);
+        result
+    }
+
+    pub fn sub_assign(&mut self, _other: &Self) {
+        if *self < *_other {
             panic!("Subtraction underflow");
         }
 
-        let max_len = self.limbs.len().max(_other.limbs.len());
-        let mut result = vec![];
-        let mut borrow: u32 = 0;
+        sub_into(&mut self.limbs, &_other.limbs);
+        self.truncate_assign();
+    }
 
-        for i in 0..max_len {
-            let mut val_self = *self.limbs.get(i).unwrap_or(&0) as u64;
-            let val_other = (*_other.limbs.get(i).unwrap_or(&0) as u64) + (borrow as u64);
-            
-            if val_self < val_other {
-                val_self += u32::MAX as u64 + 1;
-                borrow = 1;
-            } else {
-                borrow = 0;
-            }
+    pub fn try_sub(&self, _other: &Self) -> Result<Self, ArithmeticError> {
+        if *self < *_other {
+            return Err(ArithmeticError::Underflow);
+        }
+        Ok(self.sub(_other))
+    }
+
+    pub fn checked_sub(&self, _other: &Self) -> Option<Self> {
+        self.try_sub(_other).ok()
+    }
 
-            let val = val_self - val_other;
+    pub fn saturating_sub(&self, _other: &Self) -> Self {
+        self.try_sub(_other).unwrap_or_default()
+    }
 
-            result.push(val as u32);
+    // There is no width to wrap around in, so the value is |self - other|
+    // and the flag tells whether the subtraction underflowed
+    pub fn overflowing_sub(&self, _other: &Self) -> (Self, bool) {
+        match self.try_sub(_other) {
+            Ok(difference) => (difference, false),
+            Err(_) => (_other.sub(self), true),
         }
-        
-        BigUInt { limbs: result }.truncate()
     }
 
     pub fn mul_single(&self, _other: u32) -> Self {
-        let max_len = self.limbs.len();
-        let mut result = vec![];
-        let mut carry: u32 = 0;
+        let mut result = self.clone();
+        result.mul_single_assign(_other);
+        result
+    }
+
+    pub fn mul_single_assign(&mut self, _other: u32) {
+        let mut carry = 0u64;
 
-        for i in 0..max_len {
-            let product = (*self.limbs.get(i).unwrap_or(&0) as u64) * (_other as u64) + (carry as u64);
-            carry = (product >> 32) as u32;
-            result.push(product as u32);
+        for limb in self.limbs.iter_mut() {
+            let product = (*limb as u64) * (_other as u64) + carry;
+            *limb = product as u32;
+            carry = product >> 32;
         }
 
         if carry > 0 {
-            result.push(carry);
+            self.limbs.push(carry as u32);
         }
 
-        BigUInt { limbs: result }.truncate()
+        self.truncate_assign();
     }
 
-    // TODO: optimize multiplication, it's the slowest operation in the library
     pub fn mul(&self, _other: &Self) -> Self {
         if self.is_zero() || _other.is_zero() {
             return BigUInt::new();
         }
-        
-        let parts_len = _other.limbs.len(); 
-        let mut parts = vec![];
 
-        for i in 0..parts_len {
-            let part = self.mul_single(_other.limbs[i]);
-            parts.push(part);
+        BigUInt { limbs: mul_slices(&self.limbs, &_other.limbs) }.truncate()
+    }
+
+    pub fn square(&self) -> Self {
+        if self.is_zero() {
+            return BigUInt::new();
+        }
+
+        BigUInt { limbs: sqr_slices(&self.limbs) }.truncate()
+    }
+
+    pub fn pow(&self, exp: u32) -> Self {
+        self.try_pow(exp).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_pow(&self, exp: u32) -> Result<Self, ArithmeticError> {
+        self.pow_u64(exp as u64)
+    }
+
+    pub fn checked_pow(&self, exp: u32) -> Option<Self> {
+        self.try_pow(exp).ok()
+    }
+
+    pub fn pow_big(&self, exp: &BigUInt) -> Self {
+        self.try_pow_big(exp).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_pow_big(&self, exp: &BigUInt) -> Option<Self> {
+        self.try_pow_big(exp).ok()
+    }
+
+    // Only 0 and 1 survive exponents past u64, everything else is refused
+    pub fn try_pow_big(&self, exp: &BigUInt) -> Result<Self, ArithmeticError> {
+        match exp.to_u128() {
+            Some(e) if e <= u64::MAX as u128 => self.pow_u64(e as u64),
+            _ if self.is_zero() || self.bits() == 1 => Ok(self.truncate()),
+            _ => Err(ArithmeticError::ResourceLimit),
+        }
+    }
+
+    // Left-to-right binary exponentiation of the odd part, the factors of two
+    // are shifted in at the end so powers of two never multiply at all
+    fn pow_u64(&self, exp: u64) -> Result<Self, ArithmeticError> {
+        if exp == 0 {
+            return Ok(BigUInt::from_u32(1));
+        }
+        let Some(zeros) = self.trailing_zeros() else {
+            return Ok(BigUInt::new());
+        };
+
+        // the result has at least (bits - 1) * exp + 1 bits
+        let min_bits = (self.bits() as u128 - 1) * exp as u128 + 1;
+        if min_bits.div_ceil(32) > pow_limb_limit() as u128 {
+            return Err(ArithmeticError::ResourceLimit);
         }
 
-        let mut result = parts[0].clone();
+        let odd = self.shr(zeros);
+        let mut result = odd.clone();
 
-        for j in 1..parts_len {
-            result = result.add(&parts[j].shift_limbs(j));
+        if !odd.is_power_of_two() {
+            for i in (0..63 - exp.leading_zeros()).rev() {
+                result = result.square();
+                if exp >> i & 1 == 1 {
+                    result = result.mul(&odd);
+                }
+            }
         }
 
-        result.truncate()
+        Ok(result.shl(zeros * exp as usize))
     }
 
-    pub fn div_single(&self, _divisor: u32) -> (Self, u32) {
-        if _divisor == 0 {
-            panic!("Division by zero");
+    pub fn gcd(&self, _other: &Self) -> Self {
+        gcd::gcd(self, _other)
+    }
+
+    // Zero if either operand is zero
+    pub fn lcm(&self, _other: &Self) -> Self {
+        if self.is_zero() || _other.is_zero() {
+            return BigUInt::new();
         }
 
-        let max_len = self.limbs.len();
-        let mut result = vec![];
-        let mut remainder: u64 = 0;
+        self.div_rem(&self.gcd(_other)).0.mul(_other)
+    }
+
+    // (g, x, y) with self * x + other * y = g
+    pub fn extended_gcd(&self, _other: &Self) -> (Self, BigInt, BigInt) {
+        gcd::extended_gcd(self, _other)
+    }
+
+    // self^exp mod modulus, Montgomery for odd moduli and Barrett for even ones
+    pub fn modpow(&self, exp: &BigUInt, modulus: &BigUInt) -> Self {
+        modular::modpow(self, exp, modulus)
+    }
 
-        for i in (0..max_len).rev() {
-            let curr_val = (remainder << 32) + (*self.limbs.get(i).unwrap_or(&0) as u64);
-            let quotient = curr_val / (_divisor as u64);
-            remainder = curr_val % (_divisor as u64);
-            result.push(quotient as u32);
+    // The mod_* helpers accept operands of any size and return values in
+    // [0, modulus), a zero modulus panics like division does
+    pub fn mod_inverse(&self, modulus: &BigUInt) -> Option<Self> {
+        let (g, x, _) = self.div_rem(modulus).1.extended_gcd(modulus);
+        if g.bits() != 1 {
+            return None;
         }
 
-        result.reverse();
-        (BigUInt { limbs: result }.truncate(), remainder as u32)
+        let m = BigInt::from_sign_magnitude(Sign::Plus, modulus.clone());
+        Some(x.rem_euclid(&m).into_parts().1)
     }
 
-    // TODO: optimize division by finding a way to get rid of .clone() calls
-    pub fn div(&self, _other: &Self) -> (Self, Self) {
-        if _other.is_zero() {
-            panic!("Division by zero");
+    pub fn mod_add(&self, _other: &Self, modulus: &BigUInt) -> Self {
+        let mut sum = self.div_rem(modulus).1;
+        sum.add_assign(&_other.div_rem(modulus).1);
+        if sum >= *modulus {
+            sum.sub_assign(modulus);
         }
-        
-        let n = self.limbs.len();
-        let m = _other.limbs.len();
+        sum
+    }
 
-        if n < m {
-            return (BigUInt::new(), self.clone());
+    pub fn mod_sub(&self, _other: &Self, modulus: &BigUInt) -> Self {
+        let mut a = self.div_rem(modulus).1;
+        let b = _other.div_rem(modulus).1;
+        if a < b {
+            a.add_assign(modulus);
         }
+        a.sub_assign(&b);
+        a
+    }
 
-        let q_n = n - m + 1;
-        let mut q = vec![0; q_n];
+    pub fn mod_mul(&self, _other: &Self, modulus: &BigUInt) -> Self {
+        self.mul(_other).div_rem(modulus).1
+    }
 
-        let divisor_top = *_other.limbs.last().unwrap();
+    pub fn mod_neg(&self, modulus: &BigUInt) -> Self {
+        let r = self.div_rem(modulus).1;
+        if r.is_zero() {
+            r
+        } else {
+            modulus.sub(&r)
+        }
+    }
 
-        let mut remainder = self.clone();
-        remainder.limbs.push(0);
+    pub fn try_div(&self, _other: &Self) -> Result<(Self, Self), ArithmeticError> {
+        if _other.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(self.div_rem(_other))
+    }
 
-        for i in (0..q_n).rev() {
-            let u_hi = *remainder.limbs.get(i + m).unwrap_or(&0);
-            let u_lo = *remainder.limbs.get(i + m - 1).unwrap_or(&0);
+    pub fn try_rem(&self, _other: &Self) -> Result<Self, ArithmeticError> {
+        self.try_div(_other).map(|(_, r)| r)
+    }
 
-            let transient = BigUInt { limbs: vec![u_lo, u_hi] }.truncate();
+    pub fn try_div_single(&self, _divisor: u32) -> Result<(Self, u32), ArithmeticError> {
+        if _divisor == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(self.div_single(_divisor))
+    }
 
-            let mut guess = *transient.div_single(divisor_top).0.limbs.get(0).unwrap_or(&0);
+    // The quotient only, like checked_div on primitives
+    pub fn checked_div(&self, _other: &Self) -> Option<Self> {
+        self.try_div(_other).ok().map(|(q, _)| q)
+    }
 
-            q[i] = guess;
+    pub fn checked_rem(&self, _other: &Self) -> Option<Self> {
+        self.try_rem(_other).ok()
+    }
 
-            let mut tmp = _other.mul_single(guess).shift_limbs(i);
+    pub fn checked_div_single(&self, _divisor: u32) -> Option<(Self, u32)> {
+        self.try_div_single(_divisor).ok()
+    }
 
-            while tmp > remainder {
-                guess -= 1;
-                tmp = _other.mul_single(guess).shift_limbs(i);
-            }
+    pub fn div_single(&self, _divisor: u32) -> (Self, u32) {
+        let mut result = self.clone();
+        let remainder = result.div_rem_single_assign(_divisor);
+        (result, remainder)
+    }
 
-            remainder = remainder.sub(&tmp);
+    // Replaces self with the quotient and returns the remainder
+    pub fn div_rem_single_assign(&mut self, _divisor: u32) -> u32 {
+        if _divisor == 0 {
+            panic!("Division by zero");
         }
 
-        (BigUInt { limbs: q }.truncate(), remainder.truncate())
+        let remainder = div_rem_single_in_place(&mut self.limbs, _divisor);
+        self.truncate_assign();
+        remainder
     }
-}
 
-impl FromStr for BigUInt {
-    type Err = ParseBigIntError;
+    pub fn div_rem(&self, _other: &Self) -> (Self, Self) {
+        if _other.is_zero() {
+            panic!("Division by zero");
+        }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (q, r) = div_rem_slices(&self.limbs, &_other.limbs);
+        (BigUInt::from_limbs_le(q), BigUInt::from_limbs_le(r))
+    }
+
+    // The old name, which std::ops::Div shadows once the trait is in scope
+    #[deprecated(note = "use div_rem, `div` resolves to std::ops::Div when the trait is in scope")]
+    pub fn div(&self, _other: &Self) -> (Self, Self) {
+        self.div_rem(_other)
+    }
+
+    // Digits are case-insensitive, no sign or prefix is accepted
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseBigIntError::new(ParseErrorKind::InvalidRadix, 0, None));
+        }
         if s.is_empty() {
-            return Err(ParseBigIntError);
+            return Err(ParseBigIntError::new(ParseErrorKind::Empty, 0, None));
         }
+        parse_digits(s, radix, 0)
+    }
 
-        let mut result = BigUInt::from_u32(0);
-
-        for c in s.chars() {
-            let digit = c.to_digit(10).ok_or(ParseBigIntError)?;
-            
-            result = result.mul_single(10);
-            result = result.add(&BigUInt::from_u32(digit));
+    // Lowercase digits without a prefix, panics on a radix outside 2..=36
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        if !(2..=36).contains(&radix) {
+            panic!("Radix must be between 2 and 36");
         }
 
-        Ok(result.truncate())
+        let x = trim(&self.limbs);
+        let mut out = String::new();
+        if x.is_empty() {
+            out.push('0');
+        } else if radix.is_power_of_two() {
+            write_power_of_two(x, radix, &mut out);
+        } else {
+            with_radix_powers(radix, |powers| write_radix(x, 0, powers, &mut out));
+        }
+        out
     }
 }
 
-impl fmt::Display for BigUInt {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.is_zero() {
-            return write!(f, "0");
+// Low-level kernels working on little-endian limb slices. Results are not truncated.
+
+fn trim(limbs: &[u32]) -> &[u32] {
+    let len = limbs.iter().rposition(|&l| l != 0).map_or(0, |p| p + 1);
+    &limbs[..len]
+}
+
+// acc += x, returns the carry out of acc
+fn add_into(acc: &mut [u32], x: &[u32]) -> u32 {
+    let x = trim(x);
+    let mut carry = 0u64;
+
+    for (i, limb) in acc.iter_mut().enumerate() {
+        if i >= x.len() && carry == 0 {
+            break;
         }
 
-        let mut temp = self.clone();
-        let mut digits = String::new();
+        let sum = *limb as u64 + *x.get(i).unwrap_or(&0) as u64 + carry;
+        *limb = sum as u32;
+        carry = sum >> 32;
+    }
+
+    carry as u32
+}
+
+// acc -= x, returns the borrow out of acc
+fn sub_into(acc: &mut [u32], x: &[u32]) -> u32 {
+    let x = trim(x);
+    let mut borrow = 0u64;
 
-        while !temp.is_zero() {
-             let (q, r) = temp.div_single(10);
-             temp = q;
-             digits.push(std::char::from_digit(r, 10).unwrap());
-         }
+    for (i, limb) in acc.iter_mut().enumerate() {
+        if i >= x.len() && borrow == 0 {
+            break;
+        }
 
-         write!(f, "{}", digits.chars().rev().collect::<String>())
+        let diff = (*limb as u64).wrapping_sub(*x.get(i).unwrap_or(&0) as u64 + borrow);
+        *limb = diff as u32;
+        borrow = (diff >> 63) & 1;
+    }
+
+    borrow as u32
+}
+
+fn add_slices(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    result.extend_from_slice(long);
+    result.push(0);
+    add_into(&mut result, short);
+    result
+}
+
+fn mul_slices(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = (trim(a), trim(b));
+    if a == b {
+        return sqr_slices(a);
+    }
+
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    if b.is_empty() {
+        return Vec::new();
+    }
+
+    if b.len() < karatsuba_threshold() {
+        mul_schoolbook(a, b)
+    } else if b.len() >= ntt_threshold() && ntt::fits(a.len(), b.len()) {
+        ntt::mul(a, b)
+    } else if 2 * b.len() <= a.len() {
+        mul_unbalanced(a, b)
+    } else if b.len() < toom3_threshold() {
+        mul_karatsuba(a, b)
+    } else if b.len() < toom4_threshold() {
+        mul_toom3(a, b)
+    } else {
+        mul_toom4(a, b)
+    }
+}
+
+// Same tiers as mul_slices. The Karatsuba and Toom kernels notice when both
+// operands are the same slice and square their sub-products instead.
+fn sqr_slices(a: &[u32]) -> Vec<u32> {
+    let a = trim(a);
+
+    if a.is_empty() {
+        return Vec::new();
+    }
+
+    if a.len() < karatsuba_threshold() {
+        sqr_schoolbook(a)
+    } else if a.len() >= ntt_threshold() && ntt::fits(a.len(), a.len()) {
+        ntt::square(a)
+    } else if a.len() < toom3_threshold() {
+        mul_karatsuba(a, a)
+    } else if a.len() < toom4_threshold() {
+        mul_toom3(a, a)
+    } else {
+        mul_toom4(a, a)
+    }
+}
+
+// Cuts the longer operand into b-sized chunks so every sub-product is balanced
+fn mul_unbalanced(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0; a.len() + b.len()];
+
+    for (i, chunk) in a.chunks(b.len()).enumerate() {
+        add_into(&mut result[i * b.len()..], &mul_slices(chunk, b));
+    }
+
+    result
+}
+
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0; a.len() + b.len()];
+
+    for (i, &b_limb) in b.iter().enumerate() {
+        if b_limb == 0 {
+            continue;
+        }
+
+        let mut carry = 0u64;
+        for (j, &a_limb) in a.iter().enumerate() {
+            let product = (a_limb as u64) * (b_limb as u64) + (result[i + j] as u64) + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        result[i + a.len()] = carry as u32;
+    }
+
+    result
+}
+
+// Each cross product a_i*a_j (i < j) is computed once and doubled, then the
+// squares a_i^2 are added on the diagonal
+fn sqr_schoolbook(a: &[u32]) -> Vec<u32> {
+    let n = a.len();
+    let mut result = vec![0; 2 * n];
+
+    for (i, &a_limb) in a.iter().enumerate() {
+        if a_limb == 0 {
+            continue;
+        }
+
+        let mut carry = 0u64;
+        for (j, &other) in a.iter().enumerate().skip(i + 1) {
+            let product = (a_limb as u64) * (other as u64) + (result[i + j] as u64) + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        result[i + n] = carry as u32;
+    }
+
+    let mut top_bit = 0;
+    for limb in result.iter_mut() {
+        let next_top_bit = *limb >> 31;
+        *limb = (*limb << 1) | top_bit;
+        top_bit = next_top_bit;
+    }
+
+    let mut carry = 0u64;
+    for (i, &a_limb) in a.iter().enumerate() {
+        let square = (a_limb as u64) * (a_limb as u64);
+
+        let lo = (result[2 * i] as u64) + (square & 0xFFFF_FFFF) + carry;
+        result[2 * i] = lo as u32;
+
+        let hi = (result[2 * i + 1] as u64) + (square >> 32) + (lo >> 32);
+        result[2 * i + 1] = hi as u32;
+        carry = hi >> 32;
+    }
+
+    result
+}
+
+// Expects a.len() >= b.len(). Splits at half of the longer operand:
+// a*b = z2*B^2m + ((a0+a1)(b0+b1) - z0 - z2)*B^m + z0
+fn mul_karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let m = a.len().div_ceil(2);
+    let (a0, a1) = a.split_at(m);
+    let mut result = vec![0; a.len() + b.len()];
+
+    // b doesn't reach into the upper half, so only a gets split
+    if b.len() <= m {
+        add_into(&mut result, &mul_slices(a0, b));
+        add_into(&mut result[m..], &mul_slices(a1, b));
+        return result;
+    }
+
+    let (b0, b1) = b.split_at(m);
+
+    let z0 = mul_slices(a0, b0);
+    let z2 = mul_slices(a1, b1);
+
+    let mut z1 = if std::ptr::eq(a, b) {
+        sqr_slices(&add_slices(a0, a1))
+    } else {
+        mul_slices(&add_slices(a0, a1), &add_slices(b0, b1))
+    };
+    sub_into(&mut z1, &z0);
+    sub_into(&mut z1, &z2);
+
+    add_into(&mut result, &z0);
+    add_into(&mut result[m..], &z1);
+    add_into(&mut result[2 * m..], &z2);
+
+    result
+}
+
+// Shifts left by fewer than 32 bits, the result has one extra limb for the overflow
+fn shl_bits(x: &[u32], shift: u32) -> Vec<u32> {
+    let mut result = Vec::with_capacity(x.len() + 1);
+
+    if shift == 0 {
+        result.extend_from_slice(x);
+        result.push(0);
+        return result;
+    }
+
+    let mut carry = 0;
+    for &limb in x {
+        result.push((limb << shift) | carry);
+        carry = limb >> (32 - shift);
+    }
+    result.push(carry);
+
+    result
+}
+
+// Shifts right by fewer than 32 bits, dropping the shifted out bits
+fn shr_bits(x: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return x.to_vec();
+    }
+
+    let mut result = vec![0; x.len()];
+    let mut carry = 0;
+
+    for (i, &limb) in x.iter().enumerate().rev() {
+        result[i] = (limb >> shift) | carry;
+        carry = limb << (32 - shift);
+    }
+
+    result
+}
+
+// acc -= v * q where acc has one more limb than v, returns the final borrow
+fn mul_sub_into(acc: &mut [u32], v: &[u32], q: u32) -> u32 {
+    let mut carry = 0u64;
+    let mut borrow = 0u64;
+
+    for (a, &v_limb) in acc.iter_mut().zip(v) {
+        let product = (v_limb as u64) * (q as u64) + carry;
+        carry = product >> 32;
+
+        let diff = (*a as u64).wrapping_sub((product & 0xFFFF_FFFF) + borrow);
+        *a = diff as u32;
+        borrow = (diff >> 63) & 1;
+    }
+
+    let top = &mut acc[v.len()];
+    let diff = (*top as u64).wrapping_sub(carry + borrow);
+    *top = diff as u32;
+
+    ((diff >> 63) & 1) as u32
+}
+
+// Picks the division algorithm by size, v must not be zero
+fn div_rem_slices(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let (u, v) = (trim(u), trim(v));
+
+    if v.len() >= burnikel_ziegler_threshold() && u.len() >= v.len() + burnikel_ziegler_offset() {
+        div_rem_burnikel_ziegler(u, v)
+    } else {
+        div_rem_schoolbook(u, v)
+    }
+}
+
+fn div_rem_schoolbook(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let (u, v) = (trim(u), trim(v));
+
+    if cmp_slices(u, v) == Ordering::Less {
+        return (Vec::new(), u.to_vec());
+    }
+
+    if v.len() == 1 {
+        let (q, r) = div_rem_single_slice(u, v[0]);
+        return (q, vec![r]);
+    }
+
+    div_rem_knuth(u, v)
+}
+
+fn div_rem_single_slice(u: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut q = u.to_vec();
+    let r = div_rem_single_in_place(&mut q, d);
+    (q, r)
+}
+
+fn div_rem_single_in_place(x: &mut [u32], d: u32) -> u32 {
+    let mut remainder = 0u64;
+
+    for limb in x.iter_mut().rev() {
+        let current = (remainder << 32) | (*limb as u64);
+        *limb = (current / d as u64) as u32;
+        remainder = current % d as u64;
+    }
+
+    remainder as u32
+}
+
+// x = x * m + a, growing x when the carry spills over
+fn mul_single_add_in_place(x: &mut Vec<u32>, m: u32, a: u32) {
+    let mut carry = a as u64;
+
+    for limb in x.iter_mut() {
+        let product = (*limb as u64) * (m as u64) + carry;
+        *limb = product as u32;
+        carry = product >> 32;
+    }
+
+    if carry > 0 {
+        x.push(carry as u32);
+    }
+}
+
+// Knuth's Algorithm D (TAOCP vol. 2, 4.3.1). Expects trimmed u >= v with v at
+// least two limbs long. Both operands are normalized so the top bit of v is set,
+// which keeps every two-limb quotient estimate at most two too large.
+fn div_rem_knuth(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let n = v.len();
+    let m = u.len() - n;
+    let shift = v[n - 1].leading_zeros();
+
+    let mut vn = shl_bits(v, shift);
+    vn.pop();
+    let mut un = shl_bits(u, shift);
+
+    let v_hi = vn[n - 1] as u64;
+    let v_lo = vn[n - 2] as u64;
+    let mut q = vec![0; m + 1];
+
+    for j in (0..=m).rev() {
+        let numerator = ((un[j + n] as u64) << 32) | (un[j + n - 1] as u64);
+        let mut q_hat = numerator / v_hi;
+        let mut r_hat = numerator % v_hi;
+
+        while q_hat > u32::MAX as u64 || q_hat * v_lo > ((r_hat << 32) | un[j + n - 2] as u64) {
+            q_hat -= 1;
+            r_hat += v_hi;
+
+            if r_hat > u32::MAX as u64 {
+                break;
+            }
+        }
+
+        // The estimate can still be one too large, in which case v is added back
+        if mul_sub_into(&mut un[j..=j + n], &vn, q_hat as u32) != 0 {
+            q_hat -= 1;
+            add_into(&mut un[j..=j + n], &vn);
+        }
+
+        q[j] = q_hat as u32;
+    }
+
+    (q, shr_bits(&un[..n], shift))
+}
+
+// Burnikel-Ziegler recursive division ("Fast Recursive Division", 1998).
+// The divisor is padded to n = j * 2^k limbs with its top bit set, then the
+// dividend is processed in n-limb blocks with div_2n_1n, which recurses through
+// div_3n_2n until the divisor halves drop below the threshold.
+fn div_rem_burnikel_ziegler(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let s = b.len();
+    let m = (s / burnikel_ziegler_threshold() + 1).next_power_of_two();
+    let n = s.div_ceil(m) * m;
+
+    let sigma = n * 32 - bit_length(b);
+    let b = shl_slice(b, sigma);
+    let a = shl_slice(a, sigma);
+    debug_assert_eq!(b.len(), n);
+
+    // One spare bit keeps the top block below b
+    let t = ((bit_length(&a) + n * 32) / (n * 32)).max(2);
+
+    let mut q = vec![0; t * n];
+    let mut z = limb_block(&a, (t - 2) * n, t * n).to_vec();
+
+    for i in (1..t - 1).rev() {
+        let (qi, ri) = div_2n_1n(&z, &b, n);
+        add_into(&mut q[i * n..], &qi);
+
+        z = concat_limbs(limb_block(&a, (i - 1) * n, i * n), n, &ri);
+    }
+
+    let (q0, r) = div_2n_1n(&z, &b, n);
+    add_into(&mut q, &q0);
+
+    (q, shr_slice(&r, sigma))
+}
+
+// Divides a (at most 2n limbs, a < b * B^n) by b (n limbs, top bit set)
+fn div_2n_1n(a: &[u32], b: &[u32], n: usize) -> (Vec<u32>, Vec<u32>) {
+    if n % 2 == 1 || n < burnikel_ziegler_threshold() {
+        return div_rem_schoolbook(a, b);
+    }
+
+    let half = n / 2;
+
+    let (q1, r) = div_3n_2n(limb_block(a, half, a.len()), b, half);
+    let (q2, r) = div_3n_2n(&concat_limbs(limb_block(a, 0, half), half, &r), b, half);
+
+    (concat_limbs(&q2, half, &q1), r)
+}
+
+// Divides a (at most 3h limbs, a < b * B^h) by b (2h limbs, top bit set)
+fn div_3n_2n(a: &[u32], b: &[u32], h: usize) -> (Vec<u32>, Vec<u32>) {
+    let (b2, b1) = b.split_at(h);
+    let a12 = limb_block(a, h, a.len());
+    let a1 = limb_block(a, 2 * h, a.len());
+
+    let (mut q, r1) = if cmp_slices(a1, b1) == Ordering::Less {
+        div_2n_1n(a12, b1, h)
+    } else {
+        // The quotient estimate saturates at B^h - 1, r1 = a12 - b1 * B^h + b1
+        let mut r1 = add_slices(a12, b1);
+        sub_into(&mut r1[h..], b1);
+        (vec![u32::MAX; h], r1)
+    };
+
+    let d = mul_slices(&q, b2);
+    let mut r_hat = concat_limbs(limb_block(a, 0, h), h, &r1);
+    r_hat.resize(r_hat.len().max(d.len()).max(b.len()) + 1, 0);
+
+    // At most two corrections are needed
+    while cmp_slices(&r_hat, &d) == Ordering::Less {
+        add_into(&mut r_hat, b);
+        sub_into(&mut q, &[1]);
+    }
+
+    sub_into(&mut r_hat, &d);
+    (q, r_hat)
+}
+
+// x[from..to], clamped to the limbs that exist
+fn limb_block(x: &[u32], from: usize, to: usize) -> &[u32] {
+    let to = to.min(x.len());
+    &x[from.min(to)..to]
+}
+
+// hi * B^lo_len + lo, lo must fit into lo_len limbs
+fn concat_limbs(lo: &[u32], lo_len: usize, hi: &[u32]) -> Vec<u32> {
+    let lo = trim(lo);
+    let mut result = Vec::with_capacity(lo_len + hi.len());
+    result.extend_from_slice(lo);
+    result.resize(lo_len, 0);
+    result.extend_from_slice(trim(hi));
+    result
+}
+
+fn bit_length(x: &[u32]) -> usize {
+    let x = trim(x);
+    match x.last() {
+        Some(&top) => x.len() * 32 - top.leading_zeros() as usize,
+        None => 0,
+    }
+}
+
+fn shl_slice(x: &[u32], bits: usize) -> Vec<u32> {
+    let mut result = vec![0; bits / 32];
+    result.extend(shl_bits(trim(x), (bits % 32) as u32));
+    let len = trim(&result).len();
+    result.truncate(len);
+    result
+}
+
+fn shr_slice(x: &[u32], bits: usize) -> Vec<u32> {
+    shr_bits(limb_block(x, bits / 32, x.len()), (bits % 32) as u32)
+}
+
+fn cmp_slices(a: &[u32], b: &[u32]) -> Ordering {
+    let (a, b) = (trim(a), trim(b));
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+// Toom-Cook evaluation and interpolation produce negative intermediates,
+// so they work on a minimal sign-magnitude value over trimmed limbs
+#[derive(Clone)]
+struct SignedLimbs {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl SignedLimbs {
+    fn from_slice(limbs: &[u32]) -> Self {
+        SignedLimbs { negative: false, limbs: trim(limbs).to_vec() }
+    }
+
+    fn with_sign(negative: bool, mut limbs: Vec<u32>) -> Self {
+        let len = trim(&limbs).len();
+        limbs.truncate(len);
+        SignedLimbs { negative: negative && len > 0, limbs }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        if self.negative == other.negative {
+            return SignedLimbs::with_sign(self.negative, add_slices(&self.limbs, &other.limbs));
+        }
+
+        let (larger, smaller) = if cmp_slices(&self.limbs, &other.limbs) == Ordering::Less {
+            (other, self)
+        } else {
+            (self, other)
+        };
+
+        let mut limbs = larger.limbs.clone();
+        sub_into(&mut limbs, &smaller.limbs);
+        SignedLimbs::with_sign(larger.negative, limbs)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self.add(&SignedLimbs { negative: !other.negative && !other.limbs.is_empty(), limbs: other.limbs.clone() })
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        SignedLimbs::with_sign(self.negative != other.negative, mul_slices(&self.limbs, &other.limbs))
+    }
+
+    fn square(&self) -> Self {
+        SignedLimbs::with_sign(false, sqr_slices(&self.limbs))
+    }
+
+    fn mul_small(&self, n: u32) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u64;
+
+        for &limb in &self.limbs {
+            let product = (limb as u64) * (n as u64) + carry;
+            limbs.push(product as u32);
+            carry = product >> 32;
+        }
+        limbs.push(carry as u32);
+
+        SignedLimbs::with_sign(self.negative, limbs)
+    }
+
+    fn div_exact(&self, d: u32) -> Self {
+        let mut limbs = self.limbs.clone();
+        let mut remainder = 0u64;
+
+        for limb in limbs.iter_mut().rev() {
+            let current = (remainder << 32) | (*limb as u64);
+            *limb = (current / d as u64) as u32;
+            remainder = current % d as u64;
+        }
+        debug_assert_eq!(remainder, 0, "inexact Toom-Cook interpolation");
+
+        SignedLimbs::with_sign(self.negative, limbs)
+    }
+}
+
+fn toom_split<const N: usize>(x: &[u32], k: usize) -> [SignedLimbs; N] {
+    std::array::from_fn(|i| {
+        let start = (i * k).min(x.len());
+        let end = ((i + 1) * k).min(x.len());
+        SignedLimbs::from_slice(&x[start..end])
+    })
+}
+
+// Adds the (non-negative) coefficients c_i at offsets i*k
+fn toom_recompose(coefficients: &[SignedLimbs], k: usize, len: usize) -> Vec<u32> {
+    let mut result = vec![0; len];
+
+    for (i, c) in coefficients.iter().enumerate() {
+        debug_assert!(!c.negative, "negative Toom-Cook coefficient");
+        if !c.limbs.is_empty() {
+            add_into(&mut result[i * k..], &c.limbs);
+        }
+    }
+
+    result
+}
+
+// Evaluates at 0, 1, -1, -2, inf
+fn toom3_evaluate(x: &[u32], k: usize) -> [SignedLimbs; 5] {
+    let [x0, x1, x2] = toom_split::<3>(x, k);
+
+    let s = x0.add(&x2);
+    let at_1 = s.add(&x1);
+    let at_m1 = s.sub(&x1);
+    let at_m2 = at_m1.add(&x2).mul_small(2).sub(&x0);
+
+    [x0, at_1, at_m1, at_m2, x2]
+}
+
+// Expects a.len() >= b.len() > a.len() / 2
+fn mul_toom3(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let k = a.len().div_ceil(3);
+    let ea = toom3_evaluate(a, k);
+
+    let [r0, r1, rm1, rm2, rinf]: [SignedLimbs; 5] = if std::ptr::eq(a, b) {
+        std::array::from_fn(|i| ea[i].square())
+    } else {
+        let eb = toom3_evaluate(b, k);
+        std::array::from_fn(|i| ea[i].mul(&eb[i]))
+    };
+
+    // Bodrato's interpolation sequence
+    let r3 = rm2.sub(&r1).div_exact(3);
+    let r1 = r1.sub(&rm1).div_exact(2);
+    let r2 = rm1.sub(&r0);
+    let r3 = r2.sub(&r3).div_exact(2).add(&rinf.mul_small(2));
+    let r2 = r2.add(&r1).sub(&rinf);
+    let r1 = r1.sub(&r3);
+
+    toom_recompose(&[r0, r1, r2, r3, rinf], k, a.len() + b.len())
+}
+
+// Evaluates at 0, 1, -1, 2, -2, 1/2 (scaled by 8), inf
+fn toom4_evaluate(x: &[u32], k: usize) -> [SignedLimbs; 7] {
+    let [x0, x1, x2, x3] = toom_split::<4>(x, k);
+
+    let even_1 = x0.add(&x2);
+    let odd_1 = x1.add(&x3);
+    let even_2 = x0.add(&x2.mul_small(4));
+    let odd_2 = x1.mul_small(2).add(&x3.mul_small(8));
+    let at_half = x0.mul_small(8).add(&x1.mul_small(4)).add(&x2.mul_small(2)).add(&x3);
+
+    [x0, even_1.add(&odd_1), even_1.sub(&odd_1), even_2.add(&odd_2), even_2.sub(&odd_2), at_half, x3]
+}
+
+// Expects a.len() >= b.len() > a.len() / 2
+fn mul_toom4(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let k = a.len().div_ceil(4);
+    let ea = toom4_evaluate(a, k);
+
+    let [c0, r1, rm1, r2, rm2, rh, c6]: [SignedLimbs; 7] = if std::ptr::eq(a, b) {
+        std::array::from_fn(|i| ea[i].square())
+    } else {
+        let eb = toom4_evaluate(b, k);
+        std::array::from_fn(|i| ea[i].mul(&eb[i]))
+    };
+
+    // Even coefficients from the +-1 and +-2 pairs: c2+c4 and c2+4c4
+    let even_1 = r1.add(&rm1).div_exact(2).sub(&c0).sub(&c6);
+    let even_2 = r2.add(&rm2).div_exact(2).sub(&c0).sub(&c6.mul_small(64)).div_exact(4);
+    let c4 = even_2.sub(&even_1).div_exact(3);
+    let c2 = even_1.sub(&c4);
+
+    // Odd coefficients: o1 = c1+c3+c5, o2 = c1+4c3+16c5, h = 16c1+4c3+c5
+    let o1 = r1.sub(&rm1).div_exact(2);
+    let o2 = r2.sub(&rm2).div_exact(4);
+    let h = rh
+        .sub(&c0.mul_small(64))
+        .sub(&c2.mul_small(16))
+        .sub(&c4.mul_small(4))
+        .sub(&c6)
+        .div_exact(2);
+    let x = h.sub(&o1).div_exact(3);
+    let y = o2.sub(&o1).div_exact(3);
+    let d = y.sub(&x).div_exact(5);
+    let c1 = x.add(&d).sub(&o1).div_exact(3);
+    let c5 = d.add(&c1);
+    let c3 = x.sub(&c1.mul_small(5));
+
+    toom_recompose(&[c0, c1, c2, c3, c4, c5, c6], k, a.len() + b.len())
+}
+
+// Radix conversion works on chunks of the largest power of the radix that
+// fits into a limb, 10^9 for decimal. Numbers above RADIX_CONVERSION_THRESHOLD
+// limbs are split recursively by powers[k] = chunk^(2^k), so the cost follows
+// multiplication and division instead of growing quadratically. The powers are
+// cached per thread and radix up to radix_power_cache_limit limbs, so repeated
+// conversions skip the squarings. Powers of two skip all of this and map whole
+// digits onto bit ranges of the limbs.
+
+const RADIX_CONVERSION_THRESHOLD: usize = 64;
+
+struct RadixPowers {
+    radix: u32,
+    chunk: u32,
+    chunk_digits: usize,
+    powers: Vec<Vec<u32>>,
+}
+
+impl RadixPowers {
+    fn new(radix: u32) -> Self {
+        let (chunk, chunk_digits) = radix_chunk(radix);
+        RadixPowers { radix, chunk, chunk_digits, powers: vec![vec![chunk]] }
+    }
+
+    // Makes sure powers[k] exists, each entry is the square of the previous one
+    fn get(&mut self, k: usize) -> &[u32] {
+        while self.powers.len() <= k {
+            let next = sqr_slices(self.powers.last().unwrap());
+            self.powers.push(trim(&next).to_vec());
+        }
+
+        &self.powers[k]
+    }
+}
+
+thread_local! {
+    // Indexed by radix, each table is created on first use
+    static RADIX_POWERS: RefCell<Vec<Option<RadixPowers>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn with_radix_powers<T>(radix: u32, f: impl FnOnce(&mut RadixPowers) -> T) -> T {
+    RADIX_POWERS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() <= radix as usize {
+            cache.resize_with(radix as usize + 1, || None);
+        }
+        let powers = cache[radix as usize].get_or_insert_with(|| RadixPowers::new(radix));
+        let result = f(powers);
+
+        // the powers only grow, so everything from the first one over the
+        // limit goes, powers[0] always stays
+        let limit = radix_power_cache_limit();
+        let keep = powers.powers.iter().position(|p| p.len() > limit).unwrap_or(powers.powers.len());
+        powers.powers.truncate(keep.max(1));
+        result
+    })
+}
+
+// The largest power of radix that fits into a limb and its number of digits
+fn radix_chunk(radix: u32) -> (u32, usize) {
+    let (mut power, mut digits) = (radix, 1);
+    while let Some(next) = power.checked_mul(radix) {
+        power = next;
+        digits += 1;
+    }
+    (power, digits)
+}
+
+// Takes digit values, most significant first
+fn parse_radix(digits: &[u8], powers: &mut RadixPowers) -> Vec<u32> {
+    let chunk_digits = powers.chunk_digits;
+    if digits.len() <= RADIX_CONVERSION_THRESHOLD * chunk_digits {
+        return parse_radix_small(digits, powers);
+    }
+
+    let mut k = 0;
+    while chunk_digits << (k + 1) < digits.len() {
+        k += 1;
+    }
+
+    let (hi, lo) = digits.split_at(digits.len() - (chunk_digits << k));
+    let hi = parse_radix(hi, powers);
+    let lo = parse_radix(lo, powers);
+
+    let mut result = mul_slices(&hi, powers.get(k));
+    result.resize(result.len().max(lo.len()) + 1, 0);
+    add_into(&mut result, &lo);
+    result
+}
+
+fn parse_radix_small(digits: &[u8], powers: &RadixPowers) -> Vec<u32> {
+    let (radix, chunk_digits) = (powers.radix, powers.chunk_digits);
+    let mut result = Vec::with_capacity(digits.len() / chunk_digits + 1);
+    let first = match digits.len() % chunk_digits {
+        0 => chunk_digits,
+        n => n,
+    };
+
+    let (head, tail) = digits.split_at(first.min(digits.len()));
+
+    for chunk in std::iter::once(head).chain(tail.chunks(chunk_digits)) {
+        let value = chunk.iter().fold(0, |acc, &d| acc * radix + d as u32);
+        mul_single_add_in_place(&mut result, radix.pow(chunk.len() as u32), value);
+    }
+
+    result
+}
+
+// Appends the digits of x, left-padded with zeros to pad_to digits
+fn write_radix(x: &[u32], pad_to: usize, powers: &mut RadixPowers, out: &mut String) {
+    let x = trim(x);
+
+    // Smallest k with powers[k]^2 > x
+    let mut k = 0;
+    while 2 * powers.get(k).len() - 2 < x.len() {
+        k += 1;
+    }
+
+    write_radix_recursive(x, k, pad_to, powers, out);
+}
+
+// Expects x < powers[k]^2, both halves of the split are then below powers[k]
+fn write_radix_recursive(x: &[u32], k: usize, pad_to: usize, powers: &mut RadixPowers, out: &mut String) {
+    let x = trim(x);
+
+    if k == 0 || x.len() < RADIX_CONVERSION_THRESHOLD {
+        return write_radix_small(x, pad_to, powers, out);
+    }
+
+    let (hi, lo) = div_rem_slices(x, powers.get(k));
+    let lo_digits = powers.chunk_digits << k;
+
+    if pad_to == 0 && trim(&hi).is_empty() {
+        write_radix_recursive(&lo, k - 1, 0, powers, out);
+    } else {
+        write_radix_recursive(&hi, k - 1, pad_to.saturating_sub(lo_digits), powers, out);
+        write_radix_recursive(&lo, k - 1, lo_digits, powers, out);
+    }
+}
+
+fn write_radix_small(x: &[u32], pad_to: usize, powers: &RadixPowers, out: &mut String) {
+    let (radix, chunk_digits) = (powers.radix, powers.chunk_digits);
+    let mut work = x.to_vec();
+    let mut chunks = Vec::with_capacity(work.len() * 32 / chunk_digits + 1);
+
+    while !work.is_empty() {
+        chunks.push(div_rem_single_in_place(&mut work, powers.chunk));
+        let len = trim(&work).len();
+        work.truncate(len);
+    }
+
+    // least significant digit first, every chunk but the top one is padded to
+    // its full width
+    let mut digits = Vec::with_capacity(chunks.len() * chunk_digits);
+    for (i, &chunk) in chunks.iter().enumerate() {
+        let mut value = chunk;
+        let start = digits.len();
+        while value > 0 || (i + 1 < chunks.len() && digits.len() - start < chunk_digits) {
+            digits.push(char::from_digit(value % radix, radix).unwrap());
+            value /= radix;
+        }
+    }
+
+    for _ in digits.len()..pad_to {
+        out.push('0');
+    }
+    out.extend(digits.iter().rev());
+}
+
+// Takes digit values, most significant first
+fn parse_power_of_two(digits: &[u8], radix: u32) -> Vec<u32> {
+    let bits = radix.trailing_zeros();
+    let mut result = Vec::with_capacity(digits.len() * bits as usize / 32 + 1);
+    let (mut acc, mut acc_bits) = (0u64, 0);
+
+    for &d in digits.iter().rev() {
+        acc |= (d as u64) << acc_bits;
+        acc_bits += bits;
+        if acc_bits >= 32 {
+            result.push(acc as u32);
+            acc >>= 32;
+            acc_bits -= 32;
+        }
+    }
+
+    if acc_bits > 0 {
+        result.push(acc as u32);
+    }
+    result
+}
+
+// Expects a trimmed, non-zero x
+fn write_power_of_two(x: &[u32], radix: u32, out: &mut String) {
+    let bits = radix.trailing_zeros() as usize;
+    let mask = radix as u64 - 1;
+    let total_bits = 32 * x.len() - x[x.len() - 1].leading_zeros() as usize;
+
+    for i in (0..total_bits.div_ceil(bits)).rev() {
+        let (limb, shift) = (i * bits / 32, i * bits % 32);
+        let mut value = x[limb] as u64 >> shift;
+        if shift + bits > 32 && limb + 1 < x.len() {
+            value |= (x[limb + 1] as u64) << (32 - shift);
+        }
+        out.push(char::from_digit((value & mask) as u32, radix).unwrap());
+    }
+}
+
+impl FromStr for BigUInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBigIntError::new(ParseErrorKind::Empty, 0, None));
+        }
+        parse_digits(s, 10, 0)
+    }
+}
+
+// Parses a non-empty run of digits in a radix from 2..=36 that starts at byte
+// offset in the original input, so errors point into the caller's string
+pub(crate) fn parse_digits(s: &str, radix: u32, offset: usize) -> Result<BigUInt, ParseBigIntError> {
+    if let Some((i, c)) = s.char_indices().find(|&(_, c)| !c.is_digit(radix)) {
+        return Err(ParseBigIntError::new(ParseErrorKind::InvalidDigit, offset + i, Some(c)));
+    }
+
+    // log2(36) < 6, only reachable where usize is narrow
+    if s.len().checked_mul(6).is_none() {
+        return Err(ParseBigIntError::new(ParseErrorKind::TooLarge, 0, None));
+    }
+
+    // every digit is ASCII from here, so bytes and characters line up
+    let digits: Vec<u8> = s.bytes().map(|b| (b as char).to_digit(radix).unwrap() as u8).collect();
+    let limbs = if radix.is_power_of_two() {
+        parse_power_of_two(&digits, radix)
+    } else {
+        with_radix_powers(radix, |powers| parse_radix(&digits, powers))
+    };
+    Ok(BigUInt::from_limbs_le(limbs))
+}
+
+// The formatting traits go through Formatter::pad_integral, so width, fill,
+// alignment and the +, 0 and # flags behave like they do for primitives. The
+// signed variants live next to BigInt and share these helpers.
+
+pub(crate) fn fmt_radix(
+    f: &mut fmt::Formatter<'_>,
+    non_negative: bool,
+    magnitude: &BigUInt,
+    radix: u32,
+    prefix: &str,
+) -> fmt::Result {
+    f.pad_integral(non_negative, prefix, &magnitude.to_str_radix(radix))
+}
+
+pub(crate) fn fmt_upper_hex(f: &mut fmt::Formatter<'_>, non_negative: bool, magnitude: &BigUInt) -> fmt::Result {
+    f.pad_integral(non_negative, "0x", &magnitude.to_str_radix(16).to_uppercase())
+}
+
+// d.ddde<exp> with every digit by default, or rounded half to even to the
+// requested precision, the same as the primitive integers print
+pub(crate) fn fmt_exp(f: &mut fmt::Formatter<'_>, non_negative: bool, magnitude: &BigUInt) -> fmt::Result {
+    let mut digits = magnitude.to_str_radix(10).into_bytes();
+    let mut exponent = digits.len() - 1;
+
+    match f.precision() {
+        Some(precision) if precision + 1 < digits.len() => {
+            let dropped = digits.split_off(precision + 1);
+            let round_up = match dropped[0].cmp(&b'5') {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                Ordering::Equal => dropped[1..].iter().any(|&d| d != b'0') || digits[precision] % 2 == 1,
+            };
+
+            if round_up {
+                match digits.iter().rposition(|&d| d != b'9') {
+                    Some(i) => {
+                        digits[i] += 1;
+                        digits[i + 1..].fill(b'0');
+                    }
+                    // all nines carry into a new leading digit
+                    None => {
+                        digits.fill(b'0');
+                        digits[0] = b'1';
+                        exponent += 1;
+                    }
+                }
+            }
+        }
+        Some(precision) => digits.resize(precision + 1, b'0'),
+        None => {
+            let len = digits.iter().rposition(|&d| d != b'0').map_or(1, |p| p + 1);
+            digits.truncate(len);
+        }
+    }
+
+    let mut out = String::with_capacity(digits.len() + 8);
+    out.push(digits[0] as char);
+    if digits.len() > 1 {
+        out.push('.');
+        out.extend(digits[1..].iter().map(|&d| d as char));
+    }
+    out.push('e');
+    out.push_str(&exponent.to_string());
+
+    f.pad_integral(non_negative, "", &out)
+}
+
+impl fmt::Display for BigUInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_radix(f, true, self, 10, "")
+    }
+}
+
+impl fmt::LowerHex for BigUInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_radix(f, true, self, 16, "0x")
+    }
+}
+
+impl fmt::UpperHex for BigUInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_upper_hex(f, true, self)
+    }
+}
+
+impl fmt::Octal for BigUInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_radix(f, true, self, 8, "0o")
+    }
+}
+
+impl fmt::Binary for BigUInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_radix(f, true, self, 2, "0b")
+    }
+}
+
+impl fmt::LowerExp for BigUInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_exp(f, true, self)
     }
 }
 
@@ -0,0 +1,108 @@
+use crate::big_int::{BigInt, Sign};
+use crate::big_uint::{BigUInt, TryFromBigIntError};
+
+// Conversions between the big integer types and the primitive integers. Going
+// into a big integer always succeeds, coming back goes through u128 (or i128)
+// and reports whether the value was too large or negative.
+
+macro_rules! impl_from_unsigned {
+    ($($prim:ty)*) => {
+        $(
+            impl From<$prim> for BigUInt {
+                fn from(value: $prim) -> Self {
+                    BigUInt::from_u128(value as u128)
+                }
+            }
+
+            impl From<$prim> for BigInt {
+                fn from(value: $prim) -> Self {
+                    BigInt::from_u128(value as u128)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($prim:ty)*) => {
+        $(
+            impl From<$prim> for BigInt {
+                fn from(value: $prim) -> Self {
+                    BigInt::from_i128(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+fn biguint_to_u128(value: &BigUInt) -> Result<u128, TryFromBigIntError> {
+    value.to_u128().ok_or(TryFromBigIntError::Overflow)
+}
+
+fn bigint_to_i128(value: &BigInt) -> Result<i128, TryFromBigIntError> {
+    let magnitude = biguint_to_u128(value.magnitude())?;
+
+    match value.sign() {
+        Sign::Plus => i128::try_from(magnitude).map_err(|_| TryFromBigIntError::Overflow),
+        Sign::Minus if magnitude == i128::MIN.unsigned_abs() => Ok(i128::MIN),
+        Sign::Minus => i128::try_from(magnitude).map(|m| -m).map_err(|_| TryFromBigIntError::Overflow),
+    }
+}
+
+fn bigint_to_u128(value: &BigInt) -> Result<u128, TryFromBigIntError> {
+    if value.sign() == Sign::Minus && !value.magnitude().is_zero() {
+        return Err(TryFromBigIntError::Negative);
+    }
+
+    biguint_to_u128(value.magnitude())
+}
+
+macro_rules! impl_try_from_unsigned {
+    ($($prim:ty)*) => {
+        $(
+            impl TryFrom<&BigUInt> for $prim {
+                type Error = TryFromBigIntError;
+
+                fn try_from(value: &BigUInt) -> Result<Self, Self::Error> {
+                    <$prim>::try_from(biguint_to_u128(value)?).map_err(|_| TryFromBigIntError::Overflow)
+                }
+            }
+
+            impl TryFrom<&BigInt> for $prim {
+                type Error = TryFromBigIntError;
+
+                fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+                    <$prim>::try_from(bigint_to_u128(value)?).map_err(|_| TryFromBigIntError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_try_from_signed {
+    ($($prim:ty)*) => {
+        $(
+            impl TryFrom<&BigUInt> for $prim {
+                type Error = TryFromBigIntError;
+
+                fn try_from(value: &BigUInt) -> Result<Self, Self::Error> {
+                    <$prim>::try_from(biguint_to_u128(value)?).map_err(|_| TryFromBigIntError::Overflow)
+                }
+            }
+
+            impl TryFrom<&BigInt> for $prim {
+                type Error = TryFromBigIntError;
+
+                fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+                    <$prim>::try_from(bigint_to_i128(value)?).map_err(|_| TryFromBigIntError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8 u16 u32 u64 u128 usize);
+impl_from_signed!(i8 i16 i32 i64 i128 isize);
+
+impl_try_from_unsigned!(u8 u16 u32 u64 u128 usize);
+impl_try_from_signed!(i8 i16 i32 i64 i128 isize);
@@ -0,0 +1,161 @@
+// Greatest common divisors.
+//
+// Small operands use binary GCD, which only shifts and subtracts. Larger ones
+// use Lehmer's algorithm: the quotient sequence is simulated on the leading
+// 63 bits, and the collected 2x2 cofactor matrix is applied to the full
+// numbers in one pass, replacing many multi-limb divisions by a few
+// multiplications by small values. The extended variant applies the same
+// matrices to the Bezout coefficient of the first operand.
+
+use crate::big_int::{BigInt, Sign};
+use crate::big_uint::BigUInt;
+use std::mem;
+
+// Operands with at most this many limbs use binary GCD
+const LEHMER_THRESHOLD: usize = 32;
+
+// Bits of the leading part simulated by each Lehmer step
+const LEHMER_BITS: usize = 63;
+
+pub(crate) fn gcd(a: &BigUInt, b: &BigUInt) -> BigUInt {
+    if a.is_zero() {
+        return b.truncate();
+    }
+    if b.is_zero() {
+        return a.truncate();
+    }
+
+    if a.as_limbs().len().max(b.as_limbs().len()) <= LEHMER_THRESHOLD {
+        return binary_gcd(a.truncate(), b.truncate());
+    }
+
+    lehmer(a.truncate(), b.truncate(), false).0
+}
+
+// (g, x, y) with a * x + b * y = g, the coefficients are the ones Euclid's
+// algorithm produces, so |x| <= b / 2g and |y| <= a / 2g
+pub(crate) fn extended_gcd(a: &BigUInt, b: &BigUInt) -> (BigUInt, BigInt, BigInt) {
+    if b.is_zero() {
+        let x = if a.is_zero() { BigInt::new() } else { BigInt::from_u32(1) };
+        return (a.truncate(), x, BigInt::new());
+    }
+    if a.is_zero() {
+        return (b.truncate(), BigInt::new(), BigInt::from_u32(1));
+    }
+
+    let (g, x) = lehmer(a.truncate(), b.truncate(), true);
+
+    // y = (g - a * x) / b, exact by construction
+    let a_signed = BigInt::from_sign_magnitude(Sign::Plus, a.clone());
+    let rest = BigInt::from_sign_magnitude(Sign::Plus, g.clone()).sub(&a_signed.mul(&x));
+    let y = rest.div_rem(&BigInt::from_sign_magnitude(Sign::Plus, b.clone())).0;
+
+    (g, x, y)
+}
+
+fn binary_gcd(mut a: BigUInt, mut b: BigUInt) -> BigUInt {
+    let a_zeros = a.trailing_zeros().unwrap_or(0);
+    let b_zeros = b.trailing_zeros().unwrap_or(0);
+    let common = a_zeros.min(b_zeros);
+
+    a.shr_assign(a_zeros);
+    b.shr_assign(b_zeros);
+
+    // both odd from here, their difference is even
+    loop {
+        if a > b {
+            mem::swap(&mut a, &mut b);
+        }
+        b.sub_assign(&a);
+
+        match b.trailing_zeros() {
+            Some(zeros) => b.shr_assign(zeros),
+            None => break,
+        }
+    }
+
+    a.shl(common)
+}
+
+// Returns gcd(a, b) and, if tracked, the x with a * x = gcd (mod b)
+fn lehmer(mut a: BigUInt, mut b: BigUInt, track: bool) -> (BigUInt, BigInt) {
+    // a = s0 * a_orig and b = s1 * a_orig modulo b_orig
+    let (mut s0, mut s1) = (BigInt::from_u32(1), BigInt::new());
+
+    while !b.is_zero() {
+        let a_bits = a.bits();
+
+        // Lehmer needs comparable lengths, a single division gets there faster
+        if a_bits <= LEHMER_BITS || b.bits() + 32 < a_bits || b.bits() > a_bits {
+            euclid_step(&mut a, &mut b, &mut s0, &mut s1, track);
+            continue;
+        }
+
+        let shift = a_bits - LEHMER_BITS;
+        let (m00, m01, m10, m11) = lehmer_matrix(leading(&a, shift), leading(&b, shift));
+
+        if m01 == 0 {
+            euclid_step(&mut a, &mut b, &mut s0, &mut s1, track);
+            continue;
+        }
+
+        (a, b) = (combine(m00, &a, m01, &b), combine(m10, &a, m11, &b));
+
+        if track {
+            let (s0_new, s1_new) = (
+                BigInt::from(m00).mul(&s0).add(&BigInt::from(m01).mul(&s1)),
+                BigInt::from(m10).mul(&s0).add(&BigInt::from(m11).mul(&s1)),
+            );
+            (s0, s1) = (s0_new, s1_new);
+        }
+    }
+
+    (a, s0)
+}
+
+fn euclid_step(a: &mut BigUInt, b: &mut BigUInt, s0: &mut BigInt, s1: &mut BigInt, track: bool) {
+    let (q, r) = a.div_rem(b);
+    *a = mem::replace(b, r);
+
+    if track {
+        let s2 = s0.sub(&BigInt::from_sign_magnitude(Sign::Plus, q).mul(s1));
+        *s0 = mem::replace(s1, s2);
+    }
+}
+
+fn leading(x: &BigUInt, shift: usize) -> i128 {
+    u128::try_from(&x.shr(shift)).unwrap() as i128
+}
+
+// Knuth's Algorithm L: runs Euclid on the leading parts for as long as the
+// quotients provably match the ones of the full numbers
+fn lehmer_matrix(mut x: i128, mut y: i128) -> (i128, i128, i128, i128) {
+    let (mut m00, mut m01, mut m10, mut m11) = (1i128, 0i128, 0i128, 1i128);
+
+    while y + m10 != 0 && y + m11 != 0 {
+        let q = (x + m00) / (y + m10);
+        if q != (x + m01) / (y + m11) {
+            break;
+        }
+
+        (m00, m10) = (m10, m00 - q * m10);
+        (m01, m11) = (m11, m01 - q * m11);
+        (x, y) = (y, x - q * y);
+    }
+
+    (m00, m01, m10, m11)
+}
+
+// x * a + y * b for cofactors of opposite signs whose result is non-negative
+fn combine(x: i128, a: &BigUInt, y: i128, b: &BigUInt) -> BigUInt {
+    let xa = a.mul(&BigUInt::from(x.unsigned_abs()));
+    let yb = b.mul(&BigUInt::from(y.unsigned_abs()));
+
+    if x < 0 {
+        yb.sub(&xa)
+    } else if y < 0 {
+        xa.sub(&yb)
+    } else {
+        xa.add(&yb)
+    }
+}
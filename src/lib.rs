@@ -1,4 +1,9 @@
 pub mod big_uint;
 pub mod big_int;
+pub mod modular;
+mod convert;
+mod gcd;
+mod ntt;
+mod ops;
 
 pub use big_uint::BigUInt;
@@ -0,0 +1,494 @@
+// Modular exponentiation.
+//
+// Odd moduli use Montgomery multiplication: values are kept as x * R mod n
+// with R = 2^(32k), and each product is reduced with REDC, which only needs
+// limb multiplications. Even moduli use Barrett reduction with a precomputed
+// reciprocal. Both run the same sliding-window exponentiation, and both are
+// public as contexts for code that reduces against one modulus many times.
+
+use crate::big_uint::BigUInt;
+use std::cmp::Ordering;
+
+// Multiplication in some representation of the residues mod n, values are
+// converted in with enter and back out with leave
+trait Reducer {
+    fn enter(&self, x: &BigUInt) -> BigUInt;
+    fn leave(&self, x: &BigUInt) -> BigUInt;
+    fn one(&self) -> BigUInt;
+    fn mul(&self, a: &BigUInt, b: &BigUInt) -> BigUInt;
+    fn square(&self, a: &BigUInt) -> BigUInt;
+}
+
+// Montgomery arithmetic modulo a fixed odd n, with R = 2^(32k) for a k limb
+// modulus. The BigUInt methods use the fast multiplication kernels followed
+// by REDC. The *_limbs methods take residues below n as exactly k limbs and
+// write k limbs into out, multiplying with the interleaved (CIOS) form, and
+// never allocate.
+pub struct MontgomeryContext {
+    modulus: BigUInt,
+    // -n^-1 mod 2^32
+    n0_inv: u32,
+    // R^2 mod n, converts into Montgomery form with a single REDC
+    r2: BigUInt,
+    r2_limbs: Vec<u32>,
+    // R mod n, which is 1 in Montgomery form
+    r1: BigUInt,
+    one_limbs: Vec<u32>,
+}
+
+impl MontgomeryContext {
+    pub fn new(modulus: &BigUInt) -> Self {
+        if !modulus.bit(0) {
+            panic!("Montgomery modulus must be odd");
+        }
+
+        let modulus = modulus.truncate();
+        let k = modulus.as_limbs().len();
+
+        // Newton iteration doubles the number of correct low bits each step
+        let n0 = modulus.as_limbs()[0];
+        let mut inv: u32 = 1;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(n0.wrapping_mul(inv)));
+        }
+
+        let r1 = BigUInt::from_u32(1).shl(32 * k).div_rem(&modulus).1;
+        let r2 = r1.square().div_rem(&modulus).1;
+
+        let mut r2_limbs = r2.as_limbs().to_vec();
+        r2_limbs.resize(k, 0);
+        let mut one_limbs = vec![0; k];
+        one_limbs[0] = 1;
+
+        MontgomeryContext { modulus, n0_inv: inv.wrapping_neg(), r2, r2_limbs, r1, one_limbs }
+    }
+
+    pub fn modulus(&self) -> &BigUInt {
+        &self.modulus
+    }
+
+    // Length of every residue taken or written by the *_limbs methods
+    pub fn limbs(&self) -> usize {
+        self.modulus.as_limbs().len()
+    }
+
+    pub fn to_mont(&self, x: &BigUInt) -> BigUInt {
+        let x = if *x >= self.modulus { x.div_rem(&self.modulus).1 } else { x.clone() };
+        self.redc(x.mul(&self.r2))
+    }
+
+    pub fn from_mont(&self, x: &BigUInt) -> BigUInt {
+        self.reduce(x)
+    }
+
+    // Montgomery product a * b * R^-1 mod n of two residues below n
+    pub fn mul(&self, a: &BigUInt, b: &BigUInt) -> BigUInt {
+        self.redc(a.mul(b))
+    }
+
+    pub fn square(&self, a: &BigUInt) -> BigUInt {
+        self.redc(a.square())
+    }
+
+    // t * R^-1 mod n for any t
+    pub fn reduce(&self, t: &BigUInt) -> BigUInt {
+        // below 2^(bits(n) - 1) * R, so t < n * R as REDC requires
+        if t.bits() >= self.modulus.bits() + 32 * self.limbs() {
+            return self.redc(t.div_rem(&self.modulus).1);
+        }
+        self.redc(t.clone())
+    }
+
+    pub fn to_mont_limbs(&self, a: &[u32], out: &mut [u32]) {
+        self.mul_limbs(a, &self.r2_limbs, out);
+    }
+
+    pub fn from_mont_limbs(&self, a: &[u32], out: &mut [u32]) {
+        self.mul_limbs(a, &self.one_limbs, out);
+    }
+
+    pub fn square_limbs(&self, a: &[u32], out: &mut [u32]) {
+        self.mul_limbs(a, a, out);
+    }
+
+    pub fn mul_limbs(&self, a: &[u32], b: &[u32], out: &mut [u32]) {
+        let n = self.modulus.as_limbs();
+        let k = n.len();
+        assert!(a.len() == k && b.len() == k && out.len() == k, "Residues must have exactly {} limbs", k);
+
+        // out plus one extra limb holds t < 2n between rounds
+        out.fill(0);
+        let mut top = 0u32;
+
+        for &a_i in a {
+            // t += a_i * b
+            let mut carry = 0u64;
+            for (t, &b_j) in out.iter_mut().zip(b) {
+                let sum = *t as u64 + a_i as u64 * b_j as u64 + carry;
+                *t = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = top as u64 + carry;
+            top = sum as u32;
+            let overflow = (sum >> 32) as u32;
+
+            // t = (t + m * n) / 2^32, the low limb cancels by the choice of m
+            let m = out[0].wrapping_mul(self.n0_inv) as u64;
+            let mut carry = (out[0] as u64 + m * n[0] as u64) >> 32;
+            for j in 1..k {
+                let sum = out[j] as u64 + m * n[j] as u64 + carry;
+                out[j - 1] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = top as u64 + carry;
+            out[k - 1] = sum as u32;
+            top = overflow + (sum >> 32) as u32;
+        }
+
+        if top != 0 || !less(out, n) {
+            sub_in_place(out, n);
+        }
+    }
+
+    // REDC of a t < n * R given as 2k + 1 limbs, such as the product of two
+    // residues with a spare zero limb on top. t is overwritten along the way
+    // and out receives the k limbs of t * R^-1 mod n
+    pub fn reduce_limbs(&self, t: &mut [u32], out: &mut [u32]) {
+        let n = self.modulus.as_limbs();
+        let k = n.len();
+        assert!(t.len() == 2 * k + 1 && out.len() == k, "Reduction needs {} input and {} output limbs", 2 * k + 1, k);
+
+        for i in 0..k {
+            let m = t[i].wrapping_mul(self.n0_inv) as u64;
+            let mut carry = 0u64;
+
+            for (limb, &n_limb) in t[i..i + k].iter_mut().zip(n) {
+                let sum = *limb as u64 + m * n_limb as u64 + carry;
+                *limb = sum as u32;
+                carry = sum >> 32;
+            }
+
+            for limb in &mut t[i + k..] {
+                if carry == 0 {
+                    break;
+                }
+                let sum = *limb as u64 + carry;
+                *limb = sum as u32;
+                carry = sum >> 32;
+            }
+        }
+
+        out.copy_from_slice(&t[k..2 * k]);
+        if t[2 * k] != 0 || !less(out, n) {
+            sub_in_place(out, n);
+        }
+    }
+
+    // t * R^-1 mod n for t < n * R
+    fn redc(&self, t: BigUInt) -> BigUInt {
+        let k = self.limbs();
+        let mut t = t.into_limbs();
+        t.resize(2 * k + 1, 0);
+
+        let mut out = vec![0; k];
+        self.reduce_limbs(&mut t, &mut out);
+        BigUInt::from_limbs_le(out)
+    }
+}
+
+impl Reducer for MontgomeryContext {
+    fn enter(&self, x: &BigUInt) -> BigUInt {
+        self.to_mont(x)
+    }
+
+    fn leave(&self, x: &BigUInt) -> BigUInt {
+        self.from_mont(x)
+    }
+
+    fn one(&self) -> BigUInt {
+        self.r1.clone()
+    }
+
+    fn mul(&self, a: &BigUInt, b: &BigUInt) -> BigUInt {
+        MontgomeryContext::mul(self, a, b)
+    }
+
+    fn square(&self, a: &BigUInt) -> BigUInt {
+        MontgomeryContext::square(self, a)
+    }
+}
+
+// Barrett reduction modulo a fixed n of k limbs. Values of up to 2k limbs cost
+// two multiplications and at most two subtractions, larger ones fall back to
+// div_rem. The *_limbs methods take residues as exactly k limbs, write k limbs
+// into out and work in a caller-provided scratch buffer, never allocating.
+pub struct BarrettContext {
+    modulus: BigUInt,
+    k: usize,
+    // floor(2^(64k) / n)
+    mu: BigUInt,
+}
+
+impl BarrettContext {
+    pub fn new(modulus: &BigUInt) -> Self {
+        if modulus.is_zero() {
+            panic!("Division by zero");
+        }
+
+        let modulus = modulus.truncate();
+        let k = modulus.as_limbs().len();
+        let mu = BigUInt::from_u32(1).shl(64 * k).div_rem(&modulus).0;
+
+        BarrettContext { modulus, k, mu }
+    }
+
+    pub fn modulus(&self) -> &BigUInt {
+        &self.modulus
+    }
+
+    // Length of every residue taken or written by the *_limbs methods
+    pub fn limbs(&self) -> usize {
+        self.k
+    }
+
+    // Scratch length that is enough for every *_limbs method
+    pub fn scratch_limbs(&self) -> usize {
+        5 * self.k + 4
+    }
+
+    pub fn reduce(&self, x: &BigUInt) -> BigUInt {
+        let mut r = x.clone();
+        self.reduce_assign(&mut r);
+        r
+    }
+
+    // Replaces x with x mod n. The quotient estimate is built from BigUInt
+    // temporaries, so this allocates but keeps the fast multiplication kernels
+    // for large moduli, reduce_limbs is the allocation-free form
+    pub fn reduce_assign(&self, x: &mut BigUInt) {
+        if x.bits() > 64 * self.k {
+            *x = x.div_rem(&self.modulus).1;
+            return;
+        }
+
+        let q = x.shr(32 * (self.k - 1)).mul(&self.mu).shr(32 * (self.k + 1));
+        x.sub_assign(&q.mul(&self.modulus));
+
+        while *x >= self.modulus {
+            x.sub_assign(&self.modulus);
+        }
+    }
+
+    // a * b mod n for a, b below n
+    pub fn mul(&self, a: &BigUInt, b: &BigUInt) -> BigUInt {
+        let mut product = a.mul(b);
+        self.reduce_assign(&mut product);
+        product
+    }
+
+    pub fn square(&self, a: &BigUInt) -> BigUInt {
+        let mut product = a.square();
+        self.reduce_assign(&mut product);
+        product
+    }
+
+    // x mod n for any x of exactly 2k limbs, such as a product of two residues
+    pub fn reduce_limbs(&self, x: &[u32], out: &mut [u32], scratch: &mut [u32]) {
+        let k = self.k;
+        assert!(x.len() == 2 * k && out.len() == k, "Reduction needs {} input and {} output limbs", 2 * k, k);
+        self.check_scratch(scratch);
+        self.reduce_into(x, out, scratch);
+    }
+
+    // a * b mod n for a, b below n
+    pub fn mul_limbs(&self, a: &[u32], b: &[u32], out: &mut [u32], scratch: &mut [u32]) {
+        let k = self.k;
+        assert!(a.len() == k && b.len() == k && out.len() == k, "Residues must have exactly {} limbs", k);
+        self.check_scratch(scratch);
+
+        let (product, rest) = scratch.split_at_mut(2 * k);
+        mul_into(a, b, product);
+        self.reduce_into(product, out, rest);
+    }
+
+    pub fn square_limbs(&self, a: &[u32], out: &mut [u32], scratch: &mut [u32]) {
+        self.mul_limbs(a, a, out, scratch);
+    }
+
+    fn check_scratch(&self, scratch: &[u32]) {
+        let needed = self.scratch_limbs();
+        assert!(scratch.len() >= needed, "Scratch must have at least {} limbs", needed);
+    }
+
+    // Needs 3k + 4 limbs of scratch
+    fn reduce_into(&self, x: &[u32], out: &mut [u32], scratch: &mut [u32]) {
+        let k = self.k;
+        let (n, mu) = (self.modulus.as_limbs(), self.mu.as_limbs());
+        let (q2, rest) = scratch.split_at_mut(k + 1 + mu.len());
+        let r = &mut rest[..k + 1];
+
+        // q = floor(x / b^(k-1)) * mu / b^(k+1) is at most two below x / n
+        mul_into(&x[k - 1..], mu, q2);
+        let q = &q2[k + 1..];
+
+        // x - q * n is below 3n < b^(k+1), so the low k + 1 limbs are exact
+        mul_low_into(q, n, r);
+        let mut borrow = 0u64;
+        for (r_i, &x_i) in r.iter_mut().zip(x) {
+            let diff = (x_i as u64).wrapping_sub(*r_i as u64 + borrow);
+            *r_i = diff as u32;
+            borrow = (diff >> 63) & 1;
+        }
+
+        while r[k] != 0 || !less(&r[..k], n) {
+            let borrow = sub_in_place(&mut r[..k], n);
+            r[k] -= borrow;
+        }
+        out.copy_from_slice(&r[..k]);
+    }
+}
+
+impl Reducer for BarrettContext {
+    fn enter(&self, x: &BigUInt) -> BigUInt {
+        self.reduce(x)
+    }
+
+    fn leave(&self, x: &BigUInt) -> BigUInt {
+        x.clone()
+    }
+
+    fn one(&self) -> BigUInt {
+        BigUInt::from_u32(1)
+    }
+
+    fn mul(&self, a: &BigUInt, b: &BigUInt) -> BigUInt {
+        BarrettContext::mul(self, a, b)
+    }
+
+    fn square(&self, a: &BigUInt) -> BigUInt {
+        BarrettContext::square(self, a)
+    }
+}
+
+// Limb kernels for the *_limbs methods, all on little-endian slices
+
+// a < b for slices of equal length
+fn less(a: &[u32], b: &[u32]) -> bool {
+    a.iter().rev().cmp(b.iter().rev()) == Ordering::Less
+}
+
+// a -= b for slices of equal length, returns the borrow out of the top limb
+fn sub_in_place(a: &mut [u32], b: &[u32]) -> u32 {
+    let mut borrow = 0u64;
+    for (a_i, &b_i) in a.iter_mut().zip(b) {
+        let diff = (*a_i as u64).wrapping_sub(b_i as u64 + borrow);
+        *a_i = diff as u32;
+        borrow = (diff >> 63) & 1;
+    }
+    borrow as u32
+}
+
+// out = a * b, out must have a.len() + b.len() limbs
+fn mul_into(a: &[u32], b: &[u32], out: &mut [u32]) {
+    out.fill(0);
+    for (i, &a_i) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (o, &b_j) in out[i..].iter_mut().zip(b) {
+            let sum = *o as u64 + a_i as u64 * b_j as u64 + carry;
+            *o = sum as u32;
+            carry = sum >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+}
+
+// out = a * b mod 2^(32 * out.len())
+fn mul_low_into(a: &[u32], b: &[u32], out: &mut [u32]) {
+    out.fill(0);
+    let len = out.len();
+    for (i, &a_i) in a.iter().enumerate().take(len) {
+        let mut carry = 0u64;
+        for (o, &b_j) in out[i..].iter_mut().zip(b) {
+            let sum = *o as u64 + a_i as u64 * b_j as u64 + carry;
+            *o = sum as u32;
+            carry = sum >> 32;
+        }
+        if i + b.len() < len {
+            out[i + b.len()] = carry as u32;
+        }
+    }
+}
+
+// Window width for the sliding-window exponentiation, trading the table of
+// 2^(w-1) odd powers against the number of multiplications
+fn window_size(exp_bits: usize) -> usize {
+    match exp_bits {
+        0..=8 => 1,
+        9..=24 => 2,
+        25..=80 => 3,
+        81..=240 => 4,
+        241..=672 => 5,
+        _ => 6,
+    }
+}
+
+// base^exp for base < n, scanning the exponent from the top bit down
+fn pow_window<R: Reducer>(reducer: &R, base: &BigUInt, exp: &BigUInt) -> BigUInt {
+    let exp_bits = exp.bits();
+    let w = window_size(exp_bits);
+
+    let g = reducer.enter(base);
+    let g2 = reducer.square(&g);
+    let mut odd_powers = vec![g];
+    for i in 1..1 << (w - 1) {
+        odd_powers.push(reducer.mul(&odd_powers[i - 1], &g2));
+    }
+
+    let mut result = reducer.one();
+    let mut i = exp_bits;
+
+    while i > 0 {
+        if !exp.bit(i - 1) {
+            result = reducer.square(&result);
+            i -= 1;
+            continue;
+        }
+
+        // the longest window of at most w bits starting at bit i-1 and ending in a one
+        let mut start = i.saturating_sub(w);
+        while !exp.bit(start) {
+            start += 1;
+        }
+
+        let mut value = 0;
+        for bit in (start..i).rev() {
+            result = reducer.square(&result);
+            value = value << 1 | exp.bit(bit) as usize;
+        }
+
+        result = reducer.mul(&result, &odd_powers[value >> 1]);
+        i = start;
+    }
+
+    reducer.leave(&result)
+}
+
+pub(crate) fn modpow(base: &BigUInt, exp: &BigUInt, modulus: &BigUInt) -> BigUInt {
+    if modulus.is_zero() {
+        panic!("Division by zero");
+    }
+    if modulus.bits() == 1 {
+        return BigUInt::new();
+    }
+
+    let base = base.div_rem(modulus).1;
+    if exp.is_zero() {
+        return BigUInt::from_u32(1);
+    }
+
+    if modulus.bit(0) {
+        pow_window(&MontgomeryContext::new(modulus), &base, exp)
+    } else {
+        pow_window(&BarrettContext::new(modulus), &base, exp)
+    }
+}
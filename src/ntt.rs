@@ -0,0 +1,178 @@
+// Number-theoretic transform multiplication for very large operands.
+//
+// Every limb is used as one coefficient. The cyclic convolution is computed
+// modulo three NTT-friendly primes and the exact coefficients are recovered
+// with the Chinese remainder theorem (Garner's form), then carried into limbs.
+
+const P1: u64 = 998_244_353; // 119 * 2^23 + 1
+const P2: u64 = 167_772_161; // 5 * 2^25 + 1
+const P3: u64 = 469_762_049; // 7 * 2^26 + 1
+
+// 3 is a primitive root modulo all three primes
+const GENERATOR: u64 = 3;
+
+// P1 has the smallest power of two dividing p - 1. A coefficient is at most
+// min(len_a, len_b) * (2^32 - 1)^2 < 2^22 * 2^64, which stays below P1*P2*P3.
+const MAX_LEN: usize = 1 << 23;
+
+pub(crate) fn fits(a_len: usize, b_len: usize) -> bool {
+    a_len + b_len <= MAX_LEN
+}
+
+pub(crate) fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let len = a.len() + b.len();
+    let n = len.next_power_of_two();
+
+    let r1 = convolve::<P1>(a, b, n);
+    let r2 = convolve::<P2>(a, b, n);
+    let r3 = convolve::<P3>(a, b, n);
+
+    recombine(&r1, &r2, &r3, len)
+}
+
+pub(crate) fn square(a: &[u32]) -> Vec<u32> {
+    let len = 2 * a.len();
+    let n = len.next_power_of_two();
+
+    let r1 = autoconvolve::<P1>(a, n);
+    let r2 = autoconvolve::<P2>(a, n);
+    let r3 = autoconvolve::<P3>(a, n);
+
+    recombine(&r1, &r2, &r3, len)
+}
+
+fn pow_mod<const P: u64>(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    base %= P;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % P;
+        }
+        base = base * base % P;
+        exp >>= 1;
+    }
+
+    result
+}
+
+fn inverse<const P: u64>(value: u64) -> u64 {
+    pow_mod::<P>(value, P - 2)
+}
+
+fn transform<const P: u64>(values: &mut [u64], invert: bool) {
+    let n = values.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut twiddles = Vec::with_capacity(n / 2);
+    let mut len = 2;
+
+    while len <= n {
+        let half = len / 2;
+        let mut root = pow_mod::<P>(GENERATOR, (P - 1) / len as u64);
+        if invert {
+            root = inverse::<P>(root);
+        }
+
+        twiddles.clear();
+        let mut w = 1;
+        for _ in 0..half {
+            twiddles.push(w);
+            w = w * root % P;
+        }
+
+        for chunk in values.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(half);
+
+            for ((x, y), &w) in lo.iter_mut().zip(hi.iter_mut()).zip(&twiddles) {
+                let u = *x;
+                let v = *y * w % P;
+                *x = if u + v >= P { u + v - P } else { u + v };
+                *y = if u >= v { u - v } else { u + P - v };
+            }
+        }
+
+        len <<= 1;
+    }
+
+    if invert {
+        let n_inv = inverse::<P>(n as u64);
+        for v in values.iter_mut() {
+            *v = *v * n_inv % P;
+        }
+    }
+}
+
+fn forward<const P: u64>(limbs: &[u32], n: usize) -> Vec<u64> {
+    let mut values = vec![0; n];
+    for (v, &limb) in values.iter_mut().zip(limbs) {
+        *v = limb as u64 % P;
+    }
+
+    transform::<P>(&mut values, false);
+    values
+}
+
+fn convolve<const P: u64>(a: &[u32], b: &[u32], n: usize) -> Vec<u64> {
+    let mut fa = forward::<P>(a, n);
+    let fb = forward::<P>(b, n);
+
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x = *x * y % P;
+    }
+
+    transform::<P>(&mut fa, true);
+    fa
+}
+
+// Squaring only needs one forward transform
+fn autoconvolve<const P: u64>(a: &[u32], n: usize) -> Vec<u64> {
+    let mut fa = forward::<P>(a, n);
+
+    for x in fa.iter_mut() {
+        *x = *x * *x % P;
+    }
+
+    transform::<P>(&mut fa, true);
+    fa
+}
+
+fn recombine(r1: &[u64], r2: &[u64], r3: &[u64], len: usize) -> Vec<u32> {
+    let p1_inv_mod_p2 = inverse::<P2>(P1 % P2);
+    let p1p2_inv_mod_p3 = inverse::<P3>(P1 % P3 * (P2 % P3) % P3);
+    let p1p2 = P1 as u128 * P2 as u128;
+
+    let mut result = Vec::with_capacity(len);
+    let mut carry = 0u128;
+
+    for i in 0..len {
+        let (x1, x2, x3) = (r1[i], r2[i], r3[i]);
+
+        // x = x1 + P1*y1 + P1*P2*y2 with y1 < P2 and y2 < P3
+        let y1 = (x2 + P2 - x1 % P2) % P2 * p1_inv_mod_p2 % P2;
+        let partial = (x1 + P1 % P3 * y1) % P3;
+        let y2 = (x3 + P3 - partial) % P3 * p1p2_inv_mod_p3 % P3;
+
+        let value = x1 as u128 + P1 as u128 * y1 as u128 + p1p2 * y2 as u128;
+
+        carry += value;
+        result.push(carry as u32);
+        carry >>= 32;
+    }
+
+    debug_assert_eq!(carry, 0, "NTT product overflowed its length");
+    result
+}
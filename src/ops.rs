@@ -0,0 +1,215 @@
+use crate::big_int::BigInt;
+use crate::big_uint::BigUInt;
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign, Mul, MulAssign,
+    Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+// Operator overloads for BigUInt and BigInt. Everything forwards to the
+// inherent reference-taking methods, `/` and `%` pick the quotient and the
+// remainder out of `div_rem`. Like the inherent methods, BigUInt subtraction
+// panics on underflow and division panics on a zero divisor. Shifts take a
+// usize bit count, and BigInt `>>` rounds toward negative infinity. The BigInt
+// bitwise operators work on the infinite two's complement form.
+
+// Implements `T op T` for every owned/borrowed combination plus `op=`. The
+// optional last argument is an in-place method used for `op=` instead of
+// building a new value.
+macro_rules! impl_binop {
+    ($ty:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $f:expr) => {
+        impl_binop!($ty, $trait, $method, $assign_trait, $assign_method, $f, |a: &mut $ty, b: &$ty| *a = ($f)(a, b));
+    };
+    ($ty:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $f:expr, $assign_f:expr) => {
+        impl $trait<&$ty> for &$ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: &$ty) -> $ty {
+                ($f)(self, rhs)
+            }
+        }
+
+        impl $trait<$ty> for &$ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: $ty) -> $ty {
+                ($f)(self, &rhs)
+            }
+        }
+
+        impl $trait<&$ty> for $ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: &$ty) -> $ty {
+                ($f)(&self, rhs)
+            }
+        }
+
+        impl $trait<$ty> for $ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: $ty) -> $ty {
+                ($f)(&self, &rhs)
+            }
+        }
+
+        impl $assign_trait<&$ty> for $ty {
+            fn $assign_method(&mut self, rhs: &$ty) {
+                ($assign_f)(self, rhs);
+            }
+        }
+
+        impl $assign_trait<$ty> for $ty {
+            fn $assign_method(&mut self, rhs: $ty) {
+                ($assign_f)(self, &rhs);
+            }
+        }
+    };
+}
+
+// Implements `T op p`, `&T op p`, `p op T`, `p op &T` and `T op= p` for a
+// primitive p, converting it through the widest primitive of its signedness
+macro_rules! impl_primitive_binop {
+    ($ty:ty, $from_wide:path, $wide:ty, $prim:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $trait<$prim> for $ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: $prim) -> $ty {
+                <&$ty as $trait<&$ty>>::$method(&self, &$from_wide(rhs as $wide))
+            }
+        }
+
+        impl $trait<$prim> for &$ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: $prim) -> $ty {
+                <&$ty as $trait<&$ty>>::$method(self, &$from_wide(rhs as $wide))
+            }
+        }
+
+        impl $trait<$ty> for $prim {
+            type Output = $ty;
+
+            fn $method(self, rhs: $ty) -> $ty {
+                <&$ty as $trait<&$ty>>::$method(&$from_wide(self as $wide), &rhs)
+            }
+        }
+
+        impl $trait<&$ty> for $prim {
+            type Output = $ty;
+
+            fn $method(self, rhs: &$ty) -> $ty {
+                <&$ty as $trait<&$ty>>::$method(&$from_wide(self as $wide), rhs)
+            }
+        }
+
+        impl $assign_trait<$prim> for $ty {
+            fn $assign_method(&mut self, rhs: $prim) {
+                <$ty as $assign_trait<&$ty>>::$assign_method(self, &$from_wide(rhs as $wide));
+            }
+        }
+    };
+}
+
+// Implements `T op usize` and `&T op usize` plus `op=` for the bit shifts. The
+// owned form shifts in place when an in-place method is given.
+macro_rules! impl_shift {
+    ($ty:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $f:expr) => {
+        impl_shift!($ty, $trait, $method, $assign_trait, $assign_method, $f, |a: &mut $ty, bits: usize| *a = ($f)(a, bits));
+    };
+    ($ty:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $f:expr, $assign_f:expr) => {
+        impl $trait<usize> for &$ty {
+            type Output = $ty;
+
+            fn $method(self, bits: usize) -> $ty {
+                ($f)(self, bits)
+            }
+        }
+
+        impl $trait<usize> for $ty {
+            type Output = $ty;
+
+            fn $method(mut self, bits: usize) -> $ty {
+                ($assign_f)(&mut self, bits);
+                self
+            }
+        }
+
+        impl $assign_trait<usize> for $ty {
+            fn $assign_method(&mut self, bits: usize) {
+                ($assign_f)(self, bits);
+            }
+        }
+    };
+}
+
+macro_rules! impl_primitive_ops {
+    ($ty:ty, $from_wide:path, $wide:ty; $($prim:ty)*) => {
+        $(
+            impl_primitive_binop!($ty, $from_wide, $wide, $prim, Add, add, AddAssign, add_assign);
+            impl_primitive_binop!($ty, $from_wide, $wide, $prim, Sub, sub, SubAssign, sub_assign);
+            impl_primitive_binop!($ty, $from_wide, $wide, $prim, Mul, mul, MulAssign, mul_assign);
+            impl_primitive_binop!($ty, $from_wide, $wide, $prim, Div, div, DivAssign, div_assign);
+            impl_primitive_binop!($ty, $from_wide, $wide, $prim, Rem, rem, RemAssign, rem_assign);
+        )*
+    };
+}
+
+impl_binop!(BigUInt, Add, add, AddAssign, add_assign, BigUInt::add, BigUInt::add_assign);
+impl_binop!(BigUInt, Sub, sub, SubAssign, sub_assign, BigUInt::sub, BigUInt::sub_assign);
+impl_binop!(BigUInt, Mul, mul, MulAssign, mul_assign, BigUInt::mul);
+impl_binop!(BigUInt, Div, div, DivAssign, div_assign, |a: &BigUInt, b: &BigUInt| a.div_rem(b).0);
+impl_binop!(BigUInt, Rem, rem, RemAssign, rem_assign, |a: &BigUInt, b: &BigUInt| a.div_rem(b).1);
+
+impl_binop!(BigUInt, BitAnd, bitand, BitAndAssign, bitand_assign, BigUInt::bitand, BigUInt::bitand_assign);
+impl_binop!(BigUInt, BitOr, bitor, BitOrAssign, bitor_assign, BigUInt::bitor, BigUInt::bitor_assign);
+impl_binop!(BigUInt, BitXor, bitxor, BitXorAssign, bitxor_assign, BigUInt::bitxor, BigUInt::bitxor_assign);
+
+impl_binop!(BigInt, Add, add, AddAssign, add_assign, BigInt::add);
+impl_binop!(BigInt, Sub, sub, SubAssign, sub_assign, BigInt::sub);
+impl_binop!(BigInt, Mul, mul, MulAssign, mul_assign, BigInt::mul);
+impl_binop!(BigInt, Div, div, DivAssign, div_assign, |a: &BigInt, b: &BigInt| a.div_rem(b).0);
+impl_binop!(BigInt, Rem, rem, RemAssign, rem_assign, |a: &BigInt, b: &BigInt| a.div_rem(b).1);
+impl_binop!(BigInt, BitAnd, bitand, BitAndAssign, bitand_assign, BigInt::bitand);
+impl_binop!(BigInt, BitOr, bitor, BitOrAssign, bitor_assign, BigInt::bitor);
+impl_binop!(BigInt, BitXor, bitxor, BitXorAssign, bitxor_assign, BigInt::bitxor);
+
+impl_shift!(BigUInt, Shl, shl, ShlAssign, shl_assign, BigUInt::shl, BigUInt::shl_assign);
+impl_shift!(BigUInt, Shr, shr, ShrAssign, shr_assign, BigUInt::shr, BigUInt::shr_assign);
+impl_shift!(BigInt, Shl, shl, ShlAssign, shl_assign, BigInt::shl);
+impl_shift!(BigInt, Shr, shr, ShrAssign, shr_assign, BigInt::shr);
+
+impl_primitive_ops!(BigUInt, BigUInt::from_u128, u128; u8 u16 u32 u64 u128 usize);
+impl_primitive_ops!(BigInt, BigInt::from_u128, u128; u8 u16 u32 u64 u128 usize);
+impl_primitive_ops!(BigInt, BigInt::from_i128, i128; i8 i16 i32 i64 i128 isize);
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        self.negate()
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        self.negate()
+    }
+}
+
+impl Not for BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        BigInt::not(&self)
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        BigInt::not(self)
+    }
+}
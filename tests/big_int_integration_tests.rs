@@ -5,7 +5,7 @@ use std::str::FromStr;
 #[test]
 fn test_big_int_from_u32() {
     let a = BigInt::from_u32(100);
-    assert_eq!(a.sign, Sign::Plus);
+    assert_eq!(a.sign(), Sign::Plus);
     assert_eq!(a.to_string(), "100");
 }
 
@@ -22,7 +22,7 @@ fn test_big_int_from_str() {
 
     for (input, expected_sign, expected_str) in cases {
         let a = BigInt::from_str(input).unwrap();
-        assert_eq!(a.sign, expected_sign, "Sign mismatch for {}", input);
+        assert_eq!(a.sign(), expected_sign, "Sign mismatch for {}", input);
         assert_eq!(a.to_string(), expected_str, "String mismatch for {}", input);
     }
 
@@ -31,15 +31,47 @@ fn test_big_int_from_str() {
     assert!(BigInt::from_str("12a").is_err());
 }
 
+#[test]
+fn test_big_int_parse_error_kinds() {
+    use big_numbers::big_uint::ParseErrorKind;
+
+    let err = BigInt::from_str("").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::Empty);
+
+    for input in ["-", "+"] {
+        let err = BigInt::from_str(input).unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::SignOnly);
+        assert_eq!(err.position(), 1);
+        assert_eq!(err.character(), None);
+    }
+
+    // positions count the sign and are byte offsets
+    let err = BigInt::from_str("-12x4").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::InvalidDigit);
+    assert_eq!((err.position(), err.character()), (3, Some('x')));
+
+    let err = BigInt::from_str("--5").unwrap_err();
+    assert_eq!((err.kind(), err.position(), err.character()), (ParseErrorKind::InvalidDigit, 1, Some('-')));
+
+    let err = BigInt::from_str("+1é2").unwrap_err();
+    assert_eq!((err.position(), err.character()), (2, Some('é')));
+
+    assert_eq!(err.to_string(), "Invalid digit 'é' at position 2");
+    assert_eq!(BigInt::from_str("-").unwrap_err().to_string(), "Sign without digits at position 1");
+
+    let boxed: Box<dyn std::error::Error> = Box::new(err);
+    assert!(boxed.to_string().contains("position 2"));
+}
+
 #[test]
 fn test_big_int_negate() {
     let a = BigInt::from_u32(50);
     let b = a.negate();
-    assert_eq!(b.sign, Sign::Minus);
+    assert_eq!(b.sign(), Sign::Minus);
     assert_eq!(b.to_string(), "-50");
     
     let c = b.negate();
-    assert_eq!(c.sign, Sign::Plus);
+    assert_eq!(c.sign(), Sign::Plus);
     assert_eq!(c.to_string(), "50");
 }
 
@@ -47,7 +79,7 @@ fn test_big_int_negate() {
 fn test_big_int_negate_zero() {
     let a = BigInt::new();
     let b = a.negate();
-    assert!(b.magnitude.is_zero());
+    assert!(b.magnitude().is_zero());
     assert_eq!(b.to_string(), "0");
 }
 
@@ -141,8 +173,8 @@ fn test_big_int_mul_exhaustive() {
     ];
 
     for (a_str, b_str, expected) in cases {
-        let a = BigInt::from_str(a_str).expect(&format!("Failed to parse a: {}", a_str));
-        let b = BigInt::from_str(b_str).expect(&format!("Failed to parse b: {}", b_str));
+        let a = BigInt::from_str(a_str).unwrap_or_else(|_| panic!("Failed to parse a: {}", a_str));
+        let b = BigInt::from_str(b_str).unwrap_or_else(|_| panic!("Failed to parse b: {}", b_str));
         assert_eq!(a.mul(&b).to_string(), expected, "Failed: {} * {}", a_str, b_str);
     }
 }
@@ -164,12 +196,28 @@ fn test_big_int_div_exhaustive() {
     for (a_str, b_str, exp_q, exp_r) in cases {
         let a = BigInt::from_str(a_str).unwrap();
         let b = BigInt::from_str(b_str).unwrap();
-        let (q, r) = a.div(&b);
+        let (q, r) = a.div_rem(&b);
         assert_eq!(q.to_string(), exp_q, "Quotient mismatch for {} / {}", a_str, b_str);
         assert_eq!(r.to_string(), exp_r, "Remainder mismatch for {} / {}", a_str, b_str);
     }
 }
 
+#[test]
+fn test_big_int_div_rem_with_ops_in_scope() {
+    use std::ops::Div;
+
+    let a = BigInt::from(-7);
+    let b = BigInt::from(2);
+    let (q, r) = a.div_rem(&b);
+
+    assert_eq!((q.clone(), r.clone()), (BigInt::from(-3), BigInt::from(-1)));
+    assert_eq!(Div::div(&a, &b), q);
+
+    #[allow(deprecated)]
+    let old = BigInt::div(&a, &b);
+    assert_eq!(old, (q, r));
+}
+
 #[test]
 fn test_big_int_cmp_exhaustive() {
     let cases = vec![
@@ -202,3 +250,424 @@ fn test_big_int_cmp_exhaustive() {
 }
 
 
+#[test]
+fn test_big_int_operators() {
+    let a = BigInt::from_str("-123456789012345678901234567890").unwrap();
+    let b = BigInt::from_str("987654321").unwrap();
+    let (q, r) = a.div_rem(&b);
+
+    assert_eq!(&a + &b, a.add(&b));
+    assert_eq!(a.clone() - &b, a.sub(&b));
+    assert_eq!(&a * b.clone(), a.mul(&b));
+    assert_eq!(a.clone() / b.clone(), q);
+    assert_eq!(&a % &b, r);
+    assert_eq!(-&a, a.negate());
+    assert_eq!(-(-a.clone()), a);
+
+    let mut c = a.clone();
+    c += &b;
+    c -= &b;
+    c *= -1i32;
+    c /= &b;
+    assert_eq!(c, q.negate());
+}
+
+#[test]
+fn test_big_int_operators_with_primitives() {
+    let a = BigInt::from_i32(-100);
+
+    assert_eq!((&a + 1i8).to_string(), "-99");
+    assert_eq!((&a - 1u8).to_string(), "-101");
+    assert_eq!((&a * i64::MIN).to_string(), "922337203685477580800");
+    assert_eq!((&a / 7isize).to_string(), "-14");
+    assert_eq!((&a % 7i16).to_string(), "-2");
+    assert_eq!((i128::MIN + &a).to_string(), "-170141183460469231731687303715884105828");
+    assert_eq!((u128::MAX * a.clone()).to_string(), "-34028236692093846346337460743176821145500");
+    assert_eq!((-5i32 - a.clone()).to_string(), "95");
+
+    let mut b = a.clone();
+    b += 250usize;
+    b %= 40i32;
+    assert_eq!(b.to_string(), "30");
+}
+
+#[test]
+fn test_big_int_from_primitives() {
+    assert_eq!(BigInt::from(-5i8).to_string(), "-5");
+    assert_eq!(BigInt::from(i64::MIN).to_string(), i64::MIN.to_string());
+    assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+    assert_eq!(BigInt::from(i128::MAX).to_string(), i128::MAX.to_string());
+    assert_eq!(BigInt::from(u128::MAX).to_string(), u128::MAX.to_string());
+    assert_eq!(BigInt::from(isize::MIN).to_string(), isize::MIN.to_string());
+    assert_eq!(BigInt::from(0i32).sign(), Sign::Plus);
+    assert_eq!(BigInt::from(7usize).to_string(), "7");
+}
+
+#[test]
+fn test_big_int_try_from() {
+    use big_numbers::big_uint::TryFromBigIntError;
+
+    let neg = BigInt::from(-300i32);
+    assert_eq!(i16::try_from(&neg), Ok(-300));
+    assert_eq!(i8::try_from(&neg), Err(TryFromBigIntError::Overflow));
+    assert_eq!(u32::try_from(&neg), Err(TryFromBigIntError::Negative));
+    assert_eq!(u8::try_from(&neg), Err(TryFromBigIntError::Negative));
+
+    assert_eq!(i128::try_from(&BigInt::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(i128::try_from(&BigInt::from(i128::MIN).sub(&BigInt::from(1))), Err(TryFromBigIntError::Overflow));
+    assert_eq!(i64::try_from(&BigInt::from(i64::MIN)), Ok(i64::MIN));
+    assert_eq!(i64::try_from(&BigInt::from(i64::MAX as i128 + 1)), Err(TryFromBigIntError::Overflow));
+    assert_eq!(u128::try_from(&BigInt::from(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(i128::try_from(&BigInt::from(u128::MAX)), Err(TryFromBigIntError::Overflow));
+    assert_eq!(u8::try_from(&BigInt::from_str("-0").unwrap()), Ok(0));
+    assert_eq!(isize::try_from(&BigInt::from(isize::MIN)), Ok(isize::MIN));
+}
+
+#[test]
+fn test_big_int_negative_zero_is_zero() {
+    use big_numbers::BigUInt;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of(value: &BigInt) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let minus_zero = BigInt::from_sign_magnitude_unchecked(Sign::Minus, BigUInt::from_limbs_le_unchecked(vec![0, 0]));
+    let zero = BigInt::new();
+
+    assert_eq!(minus_zero, zero);
+    assert_eq!(minus_zero.cmp(&zero), Ordering::Equal);
+    assert!(minus_zero > BigInt::from(-1));
+    assert!(minus_zero < BigInt::from(1));
+    assert_eq!(hash_of(&minus_zero), hash_of(&zero));
+    assert_eq!(minus_zero.negate().sign(), Sign::Plus);
+
+    let mut set = HashSet::new();
+    set.insert(zero);
+    assert!(set.contains(&minus_zero));
+    assert!(set.contains(&BigInt::from(7).sub(&BigInt::from(7))));
+    assert!(set.contains(&BigInt::from(-7).mul(&BigInt::new())));
+}
+
+#[test]
+fn test_big_int_sign_magnitude_constructors() {
+    use big_numbers::BigUInt;
+
+    let a = BigInt::from_sign_magnitude(Sign::Minus, BigUInt::from_limbs_le_unchecked(vec![5, 0, 0]));
+    assert_eq!(a.sign(), Sign::Minus);
+    assert_eq!(a.magnitude().as_limbs(), vec![5]);
+    assert_eq!(a.to_string(), "-5");
+
+    let (sign, magnitude) = a.into_parts();
+    assert_eq!(sign, Sign::Minus);
+    assert_eq!(magnitude, BigUInt::from_u32(5));
+
+    let zero = BigInt::from_sign_magnitude(Sign::Minus, BigUInt::from_limbs_le_unchecked(vec![0, 0]));
+    assert_eq!(zero.sign(), Sign::Plus);
+    assert_eq!(zero.magnitude().as_limbs(), vec![0]);
+    assert_eq!(zero.to_string(), "0");
+
+    let raw = BigInt::from_sign_magnitude_unchecked(Sign::Minus, BigUInt::new());
+    assert_eq!(raw.sign(), Sign::Minus);
+    assert_eq!(raw, zero);
+    assert_eq!(raw.to_string(), "0");
+}
+
+#[test]
+fn test_big_int_shifts_match_i64() {
+    let values = [0i64, 1, -1, 2, -2, 7, -7, 12345, -12345, 1 << 40, -(1 << 40), i64::MAX, i64::MIN + 1, i64::MIN];
+
+    for &v in &values {
+        let a = BigInt::from(v);
+        for bits in [0usize, 1, 3, 31, 32, 33, 62, 63] {
+            assert_eq!((&a >> bits).to_string(), (v >> bits).to_string(), "{} >> {}", v, bits);
+            assert_eq!((&a << bits).to_string(), ((v as i128) << bits).to_string(), "{} << {}", v, bits);
+        }
+
+        let expected = if v < 0 { "-1" } else { "0" };
+        assert_eq!((&a >> 200).to_string(), expected, "{} >> 200", v);
+    }
+
+    let mut b = BigInt::from(-5);
+    b <<= 64;
+    assert_eq!(b.to_string(), "-92233720368547758080");
+    b >>= 66;
+    assert_eq!(b.to_string(), "-2");
+}
+
+#[test]
+fn test_big_int_bitwise_match_i128() {
+    let values = [0i128, 1, -1, 2, -2, 255, -256, 0xffff_ffff, -0xffff_ffff, 1 << 32, -(1 << 32), 0x1234_5678_9abc_def0, -0x1234_5678_9abc_def0, i64::MIN as i128, i128::MAX, i128::MIN];
+
+    for &x in &values {
+        let a = BigInt::from(x);
+        assert_eq!((!&a).to_string(), (!x).to_string(), "!{}", x);
+
+        for &y in &values {
+            let b = BigInt::from(y);
+            assert_eq!((&a & &b).to_string(), (x & y).to_string(), "{} & {}", x, y);
+            assert_eq!((&a | &b).to_string(), (x | y).to_string(), "{} | {}", x, y);
+            assert_eq!((&a ^ &b).to_string(), (x ^ y).to_string(), "{} ^ {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn test_big_int_bitwise_identities() {
+    let x = BigInt::from_str("-123456789012345678901234567890123456789").unwrap();
+    let y = BigInt::from_str("987654321098765432109876543210").unwrap();
+    let minus_one = BigInt::from(-1);
+
+    assert_eq!(&minus_one & &x, x);
+    assert_eq!(&minus_one | &y, minus_one);
+    assert_eq!(!&x, -&x - BigInt::from(1));
+    assert_eq!(!!x.clone(), x);
+    assert_eq!(&x ^ &x, BigInt::new());
+    assert_eq!((&x & &y) + (&x | &y), &x + &y);
+
+    let mut z = x.clone();
+    z &= BigInt::from(0xff);
+    assert_eq!(z.to_string(), "235");
+    z |= BigInt::from(-256);
+    assert_eq!(z.to_string(), "-21");
+    z ^= &minus_one;
+    assert_eq!(z.to_string(), "20");
+}
+
+#[test]
+fn test_big_int_bit_inspection_matches_i128() {
+    let values = [0i128, 1, -1, 2, -2, 3, -3, 255, -256, 1 << 40, -(1 << 40), 0x1234_5678_9abc_def0, -0x1234_5678_9abc_def0, i128::MAX, i128::MIN];
+
+    for &x in &values {
+        let a = BigInt::from(x);
+        let bits = if x < 0 { 128 - (!x).leading_zeros() as usize } else { 128 - x.leading_zeros() as usize };
+
+        assert_eq!(a.bits(), bits, "bits of {}", x);
+        assert_eq!(a.count_ones(), (x >= 0).then_some(x.count_ones() as usize), "count_ones of {}", x);
+        assert_eq!(a.count_zeros(), (x < 0).then_some(x.count_zeros() as usize), "count_zeros of {}", x);
+        assert_eq!(a.trailing_zeros(), (x != 0).then_some(x.trailing_zeros() as usize), "trailing_zeros of {}", x);
+
+        let positive = (x >= 0).then_some(x as u128);
+        assert_eq!(a.is_power_of_two(), positive.is_some_and(u128::is_power_of_two), "is_power_of_two of {}", x);
+        assert_eq!(a.leading_zeros(), positive.map(|p| big_numbers::BigUInt::from(p).leading_zeros()), "leading_zeros of {}", x);
+        let next = positive.map_or(BigInt::from(1), |p| BigInt::from_sign_magnitude(Sign::Plus, big_numbers::BigUInt::from(p).next_power_of_two()));
+        assert_eq!(a.next_power_of_two(), next, "next_power_of_two of {}", x);
+        let ones = positive.map(|p| (0..128).filter(|&i| p >> i & 1 == 1).collect::<Vec<_>>());
+        assert_eq!(a.iter_ones().map(|it| it.collect::<Vec<_>>()), ones, "iter_ones of {}", x);
+
+        for i in [0usize, 1, 31, 32, 33, 64, 100, 126, 127, 300] {
+            let expected = x >> i.min(127) & 1 == 1;
+            assert_eq!(a.bit(i), expected, "bit {} of {}", i, x);
+
+            for value in [false, true] {
+                if i >= 127 {
+                    continue;
+                }
+                let mut b = a.clone();
+                b.set_bit(i, value);
+                let mask = 1i128 << i;
+                let expected = if value { x | mask } else { x & !mask };
+                assert_eq!(b.to_string(), expected.to_string(), "set_bit({}, {}) of {}", i, value, x);
+            }
+        }
+    }
+
+    // a negative bitset read bit by bit, against its two's complement form
+    let d = -(BigInt::from(0x5a5a_5a5a_u32) << 4000) - BigInt::from(12345);
+    let ones = (0..4100).filter(|&i| d.bit(i)).count();
+    assert_eq!(ones, 4100 - (-&d - BigInt::from(1)).count_ones().unwrap());
+    assert!(!BigInt::from(-4).is_power_of_two());
+    assert!(d.iter_ones().is_none());
+
+    let mut c = BigInt::from(-1);
+    c.set_bit(200, false);
+    assert_eq!(c, -(BigInt::from(1) << 200) - BigInt::from(1));
+}
+
+#[test]
+fn test_big_int_pow() {
+    for base in [-7i128, -2, -1, 0, 1, 3, 10] {
+        for exp in 0..20u32 {
+            assert_eq!(BigInt::from(base).pow(exp).to_string(), base.pow(exp).to_string(), "{}^{}", base, exp);
+        }
+    }
+
+    assert_eq!(BigInt::from(-2).pow(101), -(BigInt::from(1) << 101));
+    assert_eq!(BigInt::from(-2).pow(100), BigInt::from(1) << 100);
+    assert!(BigInt::from(i64::MIN).try_pow(u32::MAX).is_err());
+    assert_eq!(BigInt::from(i64::MIN).checked_pow(u32::MAX), None);
+    assert_eq!(BigInt::from(-3).checked_pow(3), Some(BigInt::from(-27)));
+}
+
+#[test]
+fn test_big_int_modpow() {
+    let m = BigInt::from(97);
+
+    assert_eq!(BigInt::from(-3).modpow(&BigInt::from(3), &m), BigInt::from(70));
+    assert_eq!(BigInt::from(3).modpow(&BigInt::from(3), &BigInt::from(-97)), BigInt::from(27));
+    assert_eq!(BigInt::from(-3).modpow(&BigInt::from(0), &m), BigInt::from(1));
+
+    // 3 * 65 = 195 = 2 * 97 + 1
+    assert_eq!(BigInt::from(3).modpow(&BigInt::from(-1), &m), BigInt::from(65));
+    assert_eq!(BigInt::from(3).modpow(&BigInt::from(-2), &m), BigInt::from(65 * 65 % 97));
+    assert_eq!(BigInt::from(-3).modpow(&BigInt::from(-1), &m), BigInt::from(32));
+
+    let big_m = BigInt::from_str("340282366920938463463374607431768211507").unwrap();
+    let a = BigInt::from_str("-123456789012345678901234567890").unwrap();
+    let inv = a.modpow(&BigInt::from(-1), &big_m);
+    assert_eq!((&a * &inv).modpow(&BigInt::from(1), &big_m), BigInt::from(1));
+    assert_eq!(a.modpow(&BigInt::from(-5), &big_m).modpow(&BigInt::from(1), &big_m), inv.modpow(&BigInt::from(5), &big_m));
+}
+
+#[test]
+#[should_panic(expected = "Modular inverse does not exist")]
+fn test_big_int_modpow_not_invertible() {
+    BigInt::from(6).modpow(&BigInt::from(-1), &BigInt::from(9));
+}
+
+#[test]
+fn test_big_int_gcd_signs() {
+    let cases = [(12i64, 18i64, 6i64), (-12, 18, 6), (12, -18, 6), (-12, -18, 6), (0, -5, 5), (-5, 0, 5), (0, 0, 0)];
+
+    for (x, y, g) in cases {
+        let (a, b) = (BigInt::from(x), BigInt::from(y));
+        assert_eq!(a.gcd(&b), BigInt::from(g), "gcd({}, {})", x, y);
+
+        let (g2, s, t) = a.extended_gcd(&b);
+        assert_eq!(g2, BigInt::from(g), "extended gcd({}, {})", x, y);
+        assert_eq!(&a * &s + &b * &t, g2, "Bezout identity for {} and {}", x, y);
+    }
+
+    assert_eq!(BigInt::from(-4).lcm(&BigInt::from(6)), BigInt::from(12));
+    assert_eq!(BigInt::from(-4).lcm(&BigInt::new()), BigInt::new());
+
+    let a = BigInt::from_str("-123456789012345678901234567890123456789012345678901234567890").unwrap();
+    let b = BigInt::from_str("987654321098765432109876543210987654321098765432109876543210").unwrap();
+    let (g, s, t) = a.extended_gcd(&b);
+    assert_eq!(&a % &g, BigInt::new());
+    assert_eq!(&b % &g, BigInt::new());
+    assert_eq!(&a * &s + &b * &t, g);
+    assert_eq!(a.gcd(&b), g);
+}
+
+#[test]
+fn test_big_int_rem_euclid_and_mod_floor() {
+    for x in [-13i64, -10, -7, -1, 0, 1, 7, 10, 13, i64::MIN + 1, i64::MAX] {
+        for m in [-10i64, -7, -1, 1, 3, 7, 10] {
+            let (a, b) = (BigInt::from(x), BigInt::from(m));
+            assert_eq!(a.rem_euclid(&b), BigInt::from(x.rem_euclid(m)), "{} rem_euclid {}", x, m);
+
+            let floor = ((x % m) + m) % m;
+            assert_eq!(a.mod_floor(&b), BigInt::from(floor), "{} mod_floor {}", x, m);
+        }
+    }
+
+    let big = BigInt::from_str("-340282366920938463463374607431768211457").unwrap();
+    let m = BigInt::from_str("18446744073709551616").unwrap();
+    assert_eq!(big.rem_euclid(&m).to_string(), "18446744073709551615");
+    assert_eq!(big.mod_floor(&m.negate()).to_string(), "-1");
+}
+
+#[test]
+fn test_big_int_division_variants_match_i64() {
+    let dividends = [0i64, 1, -1, 5, -5, 6, -6, 7, -7, 13, -13, 100, -100, i64::MAX, i64::MIN + 1];
+    let divisors = [1i64, -1, 2, -2, 3, -3, 4, -4, 7, -7, 10, -10];
+
+    for &x in &dividends {
+        for &y in &divisors {
+            let (a, b) = (BigInt::from(x), BigInt::from(y));
+
+            let (q, r) = a.div_rem(&b);
+            assert_eq!((q, r), (BigInt::from(x / y), BigInt::from(x % y)), "{} div {}", x, y);
+
+            let floor = if x % y != 0 && (x < 0) != (y < 0) { x / y - 1 } else { x / y };
+            let (q, r) = a.div_rem_floor(&b);
+            assert_eq!(q, BigInt::from(floor), "{} div_floor {}", x, y);
+            assert_eq!(r, BigInt::from(x.wrapping_sub(floor.wrapping_mul(y))), "{} rem_floor {}", x, y);
+            assert_eq!(a.div_floor(&b), BigInt::from(floor), "{} div_floor {}", x, y);
+            assert_eq!(a.mod_floor(&b), BigInt::from(x.wrapping_sub(floor.wrapping_mul(y))), "{} mod_floor {}", x, y);
+
+            let ceil = if x % y != 0 && (x < 0) == (y < 0) { x / y + 1 } else { x / y };
+            assert_eq!(a.div_ceil(&b), BigInt::from(ceil), "{} div_ceil {}", x, y);
+
+            assert_eq!(a.div_euclid(&b), BigInt::from(x.div_euclid(y)), "{} div_euclid {}", x, y);
+            assert_eq!(a.rem_euclid(&b), BigInt::from(x.rem_euclid(y)), "{} rem_euclid {}", x, y);
+            assert_eq!(a.div_rem_euclid(&b), (BigInt::from(x.div_euclid(y)), BigInt::from(x.rem_euclid(y))), "{} div_rem_euclid {}", x, y);
+
+            let (xw, yw) = (x as i128, y as i128);
+            let (q, r) = (xw / yw, xw % yw);
+            let twice = 2 * r.abs();
+            let away = twice > yw.abs() || (twice == yw.abs() && q % 2 != 0);
+            let rounded = if !away { q } else if (xw < 0) == (yw < 0) { q + 1 } else { q - 1 };
+            assert_eq!(a.div_round(&b), BigInt::from(rounded), "{} div_round {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn test_big_int_div_round_half_even() {
+    let cases = [(5, 2, 2), (7, 2, 4), (-5, 2, -2), (-7, 2, -4), (5, -2, -2), (15, 10, 2), (25, 10, 2), (26, 10, 3), (-25, -10, 2), (1, 3, 0), (2, 3, 1)];
+
+    for (x, y, expected) in cases {
+        assert_eq!(BigInt::from(x).div_round(&BigInt::from(y)), BigInt::from(expected), "{} / {}", x, y);
+    }
+
+    let big = BigInt::from(1) << 200;
+    let half_up = &big + BigInt::from(1);
+    assert_eq!((&big * BigInt::from(3)).div_round(&(BigInt::from(2) * &big)), BigInt::from(2));
+    assert_eq!((&half_up * BigInt::from(-3)).div_round(&(BigInt::from(2) * &big)), BigInt::from(-2));
+}
+
+#[test]
+fn test_big_int_radix() {
+    use big_numbers::big_uint::ParseErrorKind;
+
+    let cases = [(i128::MIN, 16), (-255, 16), (-5, 2), (0, 7), (123456789, 36), (i128::MAX, 3)];
+    for (v, radix) in cases {
+        let a = BigInt::from(v);
+        let s = a.to_str_radix(radix);
+        assert_eq!(s, {
+            let magnitude = big_numbers::BigUInt::from(v.unsigned_abs()).to_str_radix(radix);
+            if v < 0 { format!("-{}", magnitude) } else { magnitude }
+        });
+        assert_eq!(BigInt::from_str_radix(&s, radix), Ok(a));
+    }
+
+    assert_eq!(BigInt::from_str_radix("-FF", 16).unwrap(), BigInt::from(-255));
+    assert_eq!(BigInt::from_str_radix("+zz", 36).unwrap(), BigInt::from(1295));
+    assert_eq!(BigInt::from_str_radix("-0", 2).unwrap().to_str_radix(2), "0");
+
+    assert_eq!(BigInt::from_str_radix("-", 16).unwrap_err().kind(), ParseErrorKind::SignOnly);
+    assert_eq!(BigInt::from_str_radix("12", 40).unwrap_err().kind(), ParseErrorKind::InvalidRadix);
+
+    let err = BigInt::from_str_radix("-7g", 16).unwrap_err();
+    assert_eq!((err.kind(), err.position(), err.character()), (ParseErrorKind::InvalidDigit, 2, Some('g')));
+}
+
+#[test]
+fn test_big_int_formatting_matches_primitives() {
+    for v in [0i64, 1, -1, 42, -42, 255, -255, 1234, -1200, 999, -1251, i64::MAX, i64::MIN] {
+        let a = BigInt::from(v);
+
+        assert_eq!(format!("{:x}", a), format!("{}{:x}", if v < 0 { "-" } else { "" }, v.unsigned_abs()));
+        assert_eq!(format!("{:#X}", a), format!("{}{:#X}", if v < 0 { "-" } else { "" }, v.unsigned_abs()));
+        assert_eq!(format!("{:#o}", a), format!("{}{:#o}", if v < 0 { "-" } else { "" }, v.unsigned_abs()));
+        assert_eq!(format!("{:b}", a), format!("{}{:b}", if v < 0 { "-" } else { "" }, v.unsigned_abs()));
+
+        assert_eq!(format!("{:>12}", a), format!("{:>12}", v));
+        assert_eq!(format!("{:*^13}", a), format!("{:*^13}", v));
+        assert_eq!(format!("{:+}", a), format!("{:+}", v));
+        assert_eq!(format!("{:012}", a), format!("{:012}", v));
+        assert_eq!(format!("{:e}", a), format!("{:e}", v));
+        assert_eq!(format!("{:.1e}", a), format!("{:.1e}", v));
+        assert_eq!(format!("{:+012.3e}", a), format!("{:+012.3e}", v));
+    }
+
+    assert_eq!(format!("{:#x}", BigInt::from(-255)), "-0xff");
+    assert_eq!(format!("{:#010x}", BigInt::from(-255)), "-0x00000ff");
+    assert_eq!(format!("{:x}", BigInt::from_str("-0").unwrap()), "0");
+}
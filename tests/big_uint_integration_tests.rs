@@ -18,34 +18,34 @@ fn test_exhaustive_is_zero() {
     assert!(BigUInt::new().is_zero());
     assert!(BigUInt::from_u32(0).is_zero());
     
-    assert!(BigUInt { limbs: vec![0, 0, 0] }.is_zero());
-    assert!(BigUInt { limbs: vec![0; 100] }.is_zero());
+    assert!(BigUInt::from_limbs_le_unchecked(vec![0, 0, 0]).is_zero());
+    assert!(BigUInt::from_limbs_le_unchecked(vec![0; 100]).is_zero());
     
-    assert!(BigUInt { limbs: vec![] }.is_zero());
+    assert!(BigUInt::from_limbs_le_unchecked(vec![]).is_zero());
     
     assert!(!BigUInt::from_u32(1).is_zero());
-    assert!(!BigUInt { limbs: vec![0, 0, 1] }.is_zero());
+    assert!(!BigUInt::from_limbs_le_unchecked(vec![0, 0, 1]).is_zero());
 }
 
 #[test]
 fn test_exhaustive_comparison() {
     let zero = BigUInt::new();
-    let padded_zero = BigUInt { limbs: vec![0, 0, 0] };
-    let empty_zero = BigUInt { limbs: vec![] };
+    let padded_zero = BigUInt::from_limbs_le_unchecked(vec![0, 0, 0]);
+    let empty_zero = BigUInt::from_limbs_le_unchecked(vec![]);
     
     assert_eq!(zero.cmp(&padded_zero), Ordering::Equal);
     assert_eq!(padded_zero.cmp(&empty_zero), Ordering::Equal);
     
     let five = BigUInt::from_u32(5);
-    let padded_five = BigUInt { limbs: vec![5, 0, 0, 0] };
+    let padded_five = BigUInt::from_limbs_le_unchecked(vec![5, 0, 0, 0]);
     assert_eq!(five.cmp(&padded_five), Ordering::Equal);
     
     let ten = BigUInt::from_u32(10);
     assert_eq!(ten.cmp(&padded_five), Ordering::Greater);
     assert_eq!(padded_five.cmp(&ten), Ordering::Less);
     
-    let high_val = BigUInt { limbs: vec![0, 1] };
-    let high_val_padded = BigUInt { limbs: vec![0, 1, 0, 0] };
+    let high_val = BigUInt::from_limbs_le(vec![0, 1]);
+    let high_val_padded = BigUInt::from_limbs_le_unchecked(vec![0, 1, 0, 0]);
     assert_eq!(high_val.cmp(&high_val_padded), Ordering::Equal);
 }
 
@@ -57,7 +57,7 @@ fn test_simple_addition() {
     let b = BigUInt::from_u32(20);
     let c = a.add(&b);
     
-    assert_eq!(c.limbs, vec![30]); 
+    assert_eq!(c.as_limbs(), vec![30]); 
 }
 
 #[test]
@@ -66,25 +66,25 @@ fn test_addition_with_carry() {
     let b = BigUInt::from_u32(1);
     let c = a.add(&b);
 
-    assert_eq!(c.limbs, vec![0, 1]);
+    assert_eq!(c.as_limbs(), vec![0, 1]);
 }
 
 #[test]
 fn test_cascade_carry() {
-    let a = BigUInt { limbs: vec![u32::MAX, u32::MAX] };
+    let a = BigUInt::from_limbs_le(vec![u32::MAX, u32::MAX]);
     let b = BigUInt::from_u32(1);
     let c = a.add(&b);
 
-    assert_eq!(c.limbs, vec![0, 0, 1]);
+    assert_eq!(c.as_limbs(), vec![0, 0, 1]);
 }
 
 #[test]
 fn test_carry_mid_number() {
-    let a = BigUInt { limbs: vec![u32::MAX, 10] };
+    let a = BigUInt::from_limbs_le(vec![u32::MAX, 10]);
     let b = BigUInt::from_u32(1);
     let c = a.add(&b);
 
-    assert_eq!(c.limbs, vec![0, 11]);
+    assert_eq!(c.as_limbs(), vec![0, 11]);
 }
 
 #[test]
@@ -105,12 +105,12 @@ fn test_mul_single() {
     let a = BigUInt::from_u32(10);
     let c = a.mul_single(5);
 
-    assert_eq!(c.limbs, vec![50]);
+    assert_eq!(c.as_limbs(), vec![50]);
 
     let a = BigUInt::from_u32(u32::MAX);
     let c = a.mul_single(2);
 
-    assert_eq!(c.limbs, vec![u32::MAX - 1, 1]);
+    assert_eq!(c.as_limbs(), vec![u32::MAX - 1, 1]);
 }
 
 #[test]
@@ -128,7 +128,7 @@ fn test_simple_multiplication() {
     let b = BigUInt::from_u32(20);
     let c = a.mul(&b);
 
-    assert_eq!(c.limbs, vec![200]);
+    assert_eq!(c.as_limbs(), vec![200]);
 }
 
 #[test]
@@ -137,7 +137,7 @@ fn test_mul_large() {
     let b = BigUInt::from_u32(u32::MAX);
     let c = a.mul(&b);
 
-    assert_eq!(c.limbs, vec![1, u32::MAX - 1]);
+    assert_eq!(c.as_limbs(), vec![1, u32::MAX - 1]);
 }
 
 #[test]
@@ -146,7 +146,7 @@ fn test_mul_tripling_max() {
     let b = BigUInt::from_u32(3);
     let c = a.mul(&b);
 
-    assert_eq!(c.limbs, vec![u32::MAX - 2, 2]);
+    assert_eq!(c.as_limbs(), vec![u32::MAX - 2, 2]);
 }
 
 #[test]
@@ -155,16 +155,16 @@ fn test_sub_simple() {
     let b = BigUInt::from_u32(10);
     let c = a.sub(&b);
 
-    assert_eq!(c.limbs, vec![10]);
+    assert_eq!(c.as_limbs(), vec![10]);
 }
 
 #[test]
 fn test_sub_borrow() {
-    let a = BigUInt { limbs: vec![0, 1] };
+    let a = BigUInt::from_limbs_le(vec![0, 1]);
     let b = BigUInt::from_u32(1);
     let c = a.sub(&b);
 
-    assert_eq!(c.limbs, vec![u32::MAX]);
+    assert_eq!(c.as_limbs(), vec![u32::MAX]);
 }
 
 #[test]
@@ -177,20 +177,20 @@ fn test_sub_underflow() {
 
 #[test]
 fn test_sub_cascade_borrow() {
-    let a = BigUInt { limbs: vec![0, 0, 1] };
+    let a = BigUInt::from_limbs_le(vec![0, 0, 1]);
     let b = BigUInt::from_u32(1);
     let c = a.sub(&b);
     
-    assert_eq!(c.limbs, vec![u32::MAX, u32::MAX]);
+    assert_eq!(c.as_limbs(), vec![u32::MAX, u32::MAX]);
 }
 
 #[test]
 fn test_sub_large_diff() {
-    let a = BigUInt { limbs: vec![10, 5] };
+    let a = BigUInt::from_limbs_le(vec![10, 5]);
     let b = BigUInt::from_u32(20);
     let c = a.sub(&b);
     
-    assert_eq!(c.limbs, vec![u32::MAX - 9, 4]);
+    assert_eq!(c.as_limbs(), vec![u32::MAX - 9, 4]);
 }
 
 #[test]
@@ -198,7 +198,7 @@ fn test_div_single_simple() {
     let a = BigUInt::from_u32(100);
     let (q, r) = a.div_single(5);
     
-    assert_eq!(q.limbs, vec![20]);
+    assert_eq!(q.as_limbs(), vec![20]);
     assert_eq!(r, 0);
 }
 
@@ -207,16 +207,16 @@ fn test_div_single_with_remainder() {
     let a = BigUInt::from_u32(102);
     let (q, r) = a.div_single(5);
     
-    assert_eq!(q.limbs, vec![20]);
+    assert_eq!(q.as_limbs(), vec![20]);
     assert_eq!(r, 2);
 }
 
 #[test]
 fn test_div_single_large() {
-    let a = BigUInt { limbs: vec![0, 1] };
+    let a = BigUInt::from_limbs_le(vec![0, 1]);
     let (q, r) = a.div_single(2);
     
-    assert_eq!(q.limbs, vec![2147483648]);
+    assert_eq!(q.as_limbs(), vec![2147483648]);
     assert_eq!(r, 0);
 }
 
@@ -224,7 +224,7 @@ fn test_div_single_large() {
 fn test_div_single_by_one() {
     let a = BigUInt::from_u32(12345);
     let (q, r) = a.div_single(1);
-    assert_eq!(q.limbs, vec![12345]);
+    assert_eq!(q.as_limbs(), vec![12345]);
     assert_eq!(r, 0);
 }
 
@@ -255,91 +255,106 @@ fn test_div_single_by_zero() {
 fn test_div_simple() {
     let a = BigUInt::from_u32(200);
     let b = BigUInt::from_u32(20);
-    let (q, r) = a.div(&b);
+    let (q, r) = a.div_rem(&b);
     
-    assert_eq!(q.limbs, vec![10]);
+    assert_eq!(q.as_limbs(), vec![10]);
     assert!(r.is_zero());
 }
 
+#[test]
+fn test_div_rem_with_ops_in_scope() {
+    use std::ops::{Div, Rem};
+
+    let a = BigUInt::from_u32(205);
+    let b = BigUInt::from_u32(20);
+    let (q, r) = a.div_rem(&b);
+
+    assert_eq!(Div::div(&a, &b), q);
+    assert_eq!(Rem::rem(&a, &b), r);
+    assert_eq!(q.as_limbs(), vec![10]);
+    assert_eq!(r.as_limbs(), vec![5]);
+
+    #[allow(deprecated)]
+    let old = BigUInt::div(&a, &b);
+    assert_eq!(old, (q, r));
+}
+
 #[test]
 fn test_div_with_remainder() {
     let a = BigUInt::from_u32(205);
     let b = BigUInt::from_u32(20);
-    let (q, r) = a.div(&b);
+    let (q, r) = a.div_rem(&b);
     
-    assert_eq!(q.limbs, vec![10]);
-    assert_eq!(r.limbs, vec![5]);
+    assert_eq!(q.as_limbs(), vec![10]);
+    assert_eq!(r.as_limbs(), vec![5]);
 }
 
 #[test]
 fn test_div_large() {
     let a = BigUInt::from_u32(1187);
     let b = BigUInt::from_u32(37);
-    let (q, r) = a.div(&b);
+    let (q, r) = a.div_rem(&b);
     
-    assert_eq!(q.limbs, vec![32]);
-    assert_eq!(r.limbs, vec![3]);
+    assert_eq!(q.as_limbs(), vec![32]);
+    assert_eq!(r.as_limbs(), vec![3]);
 }
 
 #[test]
 fn test_div_small_by_large() {
     let a = BigUInt::from_u32(10);
     let b = BigUInt::from_u32(20);
-    let (q, r) = a.div(&b);
+    let (q, r) = a.div_rem(&b);
     
     assert!(q.is_zero());
-    assert_eq!(r.limbs, vec![10]);
+    assert_eq!(r.as_limbs(), vec![10]);
 }
 
 #[test]
 fn test_div_multi_limb_quotient() {
-    let a = BigUInt { limbs: vec![0, 2] };
+    let a = BigUInt::from_limbs_le(vec![0, 2]);
     let b = BigUInt::from_u32(1);
-    let (q, r) = a.div(&b);
+    let (q, r) = a.div_rem(&b);
 
-    assert_eq!(q.limbs, vec![0, 2]);
+    assert_eq!(q.as_limbs(), vec![0, 2]);
     assert!(r.is_zero());
 }
 
 #[test]
 fn test_div_self() {
-    let a = BigUInt { limbs: vec![123, 456, 789] };
-    let (q, r) = a.div(&a);
+    let a = BigUInt::from_limbs_le(vec![123, 456, 789]);
+    let (q, r) = a.div_rem(&a);
 
-    assert_eq!(q.limbs, vec![1]);
+    assert_eq!(q.as_limbs(), vec![1]);
     assert!(r.is_zero());
 }
 
 #[test]
 fn test_div_exact_multiple_large() {
     let list = vec![u32::MAX, u32::MAX];
-    let b = BigUInt { limbs: list.clone() };
+    let b = BigUInt::from_limbs_le(list.clone());
     
-    let a = BigUInt { limbs: vec![12345, 67890] };
+    let a = BigUInt::from_limbs_le(vec![12345, 67890]);
     let product = a.mul(&b);
     
-    let (q, r) = product.div(&b);
+    let (q, r) = product.div_rem(&b);
 
-    assert_eq!(q.limbs, a.limbs);
+    assert_eq!(q.as_limbs(), a.as_limbs());
     assert!(r.is_zero());
 }
 
 #[test]
 fn test_div_correction_case() {
-    let v_hi = 2147483648u32; // 2^31
-    let divisor = BigUInt { limbs: vec![0, v_hi] };
-    
-    let a = BigUInt { limbs: vec![
+    let a = BigUInt::from_limbs_le(vec![
         0, 0, 0, 
         u32::MAX, u32::MAX
-    ]};
-    let b = BigUInt { limbs: vec![u32::MAX] };
+    ]);
+    let b = BigUInt::from_limbs_le(vec![u32::MAX]);
     
-    let (q, r) = a.div(&b);
+    let (q, r) = a.div_rem(&b);
     
     let check = b.mul(&q).add(&r);
 
-    assert_eq!(check.limbs, a.limbs);
+    assert_eq!(check.as_limbs(), a.as_limbs());
     assert!(r < b);
 }
 
@@ -347,7 +362,7 @@ fn test_div_correction_case() {
 fn test_from_str_simple() {
     let a = BigUInt::from_str("12345").unwrap();
     
-    assert_eq!(a.limbs, vec![12345]);
+    assert_eq!(a.as_limbs(), vec![12345]);
 }
 
 #[test]
@@ -355,7 +370,7 @@ fn test_from_str_large() {
     let s = "18446744073709551616";
     let a = BigUInt::from_str(s).unwrap();
     
-    assert_eq!(a.limbs, vec![0, 0, 1]);
+    assert_eq!(a.as_limbs(), vec![0, 0, 1]);
 }
 
 #[test]
@@ -365,12 +380,30 @@ fn test_from_str_invalid() {
     assert!(a.is_err());
 }
 
+#[test]
+fn test_from_str_error_details() {
+    use big_numbers::big_uint::ParseErrorKind;
+
+    let err = BigUInt::from_str("123a45").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::InvalidDigit);
+    assert_eq!(err.position(), 3);
+    assert_eq!(err.character(), Some('a'));
+
+    let err = BigUInt::from_str("").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::Empty);
+    assert_eq!(err.to_string(), "Cannot parse a number from an empty string");
+
+    // BigUInt takes no sign at all
+    let err = BigUInt::from_str("+7").unwrap_err();
+    assert_eq!((err.kind(), err.position(), err.character()), (ParseErrorKind::InvalidDigit, 0, Some('+')));
+}
+
 #[test]
 fn test_from_str_zero() {
     let a = BigUInt::from_str("0").unwrap();
 
     assert!(a.is_zero());
-    assert_eq!(a.limbs, vec![0]);
+    assert_eq!(a.as_limbs(), vec![0]);
 }
 
 #[test]
@@ -388,7 +421,7 @@ fn test_display_zero() {
 #[test]
 fn test_display_large() {
     // 2^256 big number ;o
-    let a = BigUInt { limbs: vec![0, 0, 0, 0, 0, 0, 0, 0, 1] };
+    let a = BigUInt::from_limbs_le(vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
     let expected = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
     assert_eq!(a.to_string(), expected);
 }
@@ -400,4 +433,1081 @@ fn test_display_roundtrip() {
     assert_eq!(a.to_string(), s);
 }
 
+#[test]
+fn test_decimal_conversion_reuses_powers() {
+    // the power table outlives each call, mixed sizes must keep working off it
+    for digits in [20000, 50, 5000, 40000, 700, 20000] {
+        let s: String = (0..digits).map(|i| char::from(b'1' + (i * 7 % 9) as u8)).collect();
+        let a = BigUInt::from_str(&s).unwrap();
+        assert_eq!(a.to_string(), s, "{} digits", digits);
+    }
+}
+
+fn pseudo_random_limbs(len: usize, seed: u64) -> Vec<u32> {
+    let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 16) as u32
+        })
+        .collect()
+}
+
+fn reference_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    while result.len() > 1 && result.last() == Some(&0) {
+        result.pop();
+    }
+    result
+}
+
+#[test]
+fn test_mul_karatsuba_matches_schoolbook() {
+    let sizes = [(32, 32), (33, 47), (64, 64), (100, 37), (257, 255), (300, 120), (500, 499)];
+
+    for (seed, &(n, m)) in sizes.iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64));
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(m, seed as u64 + 100));
+
+        assert_eq!(a.mul(&b).as_limbs(), reference_mul(a.as_limbs(), b.as_limbs()), "Mismatch for {}x{} limbs", n, m);
+        assert_eq!(b.mul(&a).as_limbs(), reference_mul(a.as_limbs(), b.as_limbs()), "Mismatch for {}x{} limbs", m, n);
+    }
+}
+
+#[test]
+fn test_mul_karatsuba_all_ones() {
+    // (B^n - 1)^2 = B^2n - 2B^n + 1 exercises every carry path
+    let n = 200;
+    let a = BigUInt::from_limbs_le(vec![u32::MAX; n]);
+    let c = a.mul(&a);
+
+    let mut expected = vec![0u32; 2 * n];
+    expected[0] = 1;
+    expected[n] = u32::MAX - 1;
+    for limb in expected.iter_mut().skip(n + 1) {
+        *limb = u32::MAX;
+    }
+
+    assert_eq!(c.as_limbs(), expected);
+}
+
+#[test]
+fn test_mul_karatsuba_threshold_is_tunable() {
+    let default = big_numbers::big_uint::karatsuba_threshold();
+    assert!(default >= 2);
+
+    let a = BigUInt::from_limbs_le(pseudo_random_limbs(90, 7));
+    let b = BigUInt::from_limbs_le(pseudo_random_limbs(70, 8));
+    let expected = reference_mul(a.as_limbs(), b.as_limbs());
+
+    big_numbers::big_uint::set_karatsuba_threshold(0);
+    assert_eq!(big_numbers::big_uint::karatsuba_threshold(), 2);
+    assert_eq!(a.mul(&b).as_limbs(), expected);
+
+    big_numbers::big_uint::set_karatsuba_threshold(default);
+    assert_eq!(a.mul(&b).as_limbs(), expected);
+}
+
+#[test]
+fn test_mul_toom_matches_schoolbook() {
+    let sizes = [(300, 300), (301, 299), (450, 420), (1000, 1000), (1003, 700), (1500, 1499)];
+
+    for (seed, &(n, m)) in sizes.iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 10));
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(m, seed as u64 + 200));
+
+        assert_eq!(a.mul(&b).as_limbs(), reference_mul(a.as_limbs(), b.as_limbs()), "Mismatch for {}x{} limbs", n, m);
+    }
+}
+
+#[test]
+fn test_mul_toom_all_ones() {
+    for n in [300, 1000, 1201] {
+        let a = BigUInt::from_limbs_le(vec![u32::MAX; n]);
+        let b = BigUInt::from_limbs_le(vec![u32::MAX; n - 1]);
+
+        assert_eq!(a.mul(&b).as_limbs(), reference_mul(a.as_limbs(), b.as_limbs()), "Mismatch for {} limbs", n);
+    }
+}
+
+#[test]
+fn test_mul_unbalanced() {
+    let sizes = [(2000, 40), (1000, 499), (5000, 300), (777, 33)];
+
+    for (seed, &(n, m)) in sizes.iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 30));
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(m, seed as u64 + 300));
+        let expected = reference_mul(a.as_limbs(), b.as_limbs());
+
+        assert_eq!(a.mul(&b).as_limbs(), expected, "Mismatch for {}x{} limbs", n, m);
+        assert_eq!(b.mul(&a).as_limbs(), expected, "Mismatch for {}x{} limbs", m, n);
+    }
+}
+
+#[test]
+fn test_mul_ntt_matches_schoolbook() {
+    let sizes = [(2000, 2000), (2500, 2100), (4100, 2050)];
+
+    for (seed, &(n, m)) in sizes.iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 50));
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(m, seed as u64 + 500));
+
+        assert_eq!(a.mul(&b).as_limbs(), reference_mul(a.as_limbs(), b.as_limbs()), "Mismatch for {}x{} limbs", n, m);
+    }
+}
+
+#[test]
+fn test_mul_ntt_all_ones() {
+    // all-ones limbs give the largest possible convolution coefficients
+    let n = 4096;
+    let a = BigUInt::from_limbs_le(vec![u32::MAX; n]);
+    let c = a.mul(&a);
+
+    let mut expected = vec![u32::MAX; 2 * n];
+    expected[0] = 1;
+    expected[n] = u32::MAX - 1;
+    for limb in expected.iter_mut().take(n).skip(1) {
+        *limb = 0;
+    }
+
+    assert_eq!(c.as_limbs(), expected);
+}
+
+#[test]
+fn test_square_simple() {
+    assert!(BigUInt::new().square().is_zero());
+    assert_eq!(BigUInt::from_u32(12345).square().as_limbs(), vec![152399025]);
+    assert_eq!(BigUInt::from_u32(u32::MAX).square().as_limbs(), vec![1, u32::MAX - 1]);
+    assert_eq!(BigUInt::from_limbs_le_unchecked(vec![0, 1, 0]).square().as_limbs(), vec![0, 0, 1]);
+}
+
+#[test]
+fn test_square_matches_mul_across_tiers() {
+    for (seed, n) in [1usize, 2, 31, 32, 100, 299, 300, 999, 1000, 2000, 2500].into_iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 70));
+        let expected = reference_mul(a.as_limbs(), a.as_limbs());
+
+        assert_eq!(a.square().as_limbs(), expected, "square mismatch for {} limbs", n);
+        assert_eq!(a.mul(&a.clone()).as_limbs(), expected, "self-mul mismatch for {} limbs", n);
+    }
+}
+
+#[test]
+fn test_square_all_ones() {
+    for n in [20, 64, 400, 1200, 3000] {
+        let a = BigUInt::from_limbs_le(vec![u32::MAX; n]);
+        assert_eq!(a.square().as_limbs(), reference_mul(a.as_limbs(), a.as_limbs()), "Mismatch for {} limbs", n);
+    }
+}
+
+fn assert_div_rem(a: &BigUInt, b: &BigUInt) {
+    let (q, r) = a.div_rem(b);
+
+    assert!(r < *b, "remainder not reduced for {:?} / {:?}", a.as_limbs(), b.as_limbs());
+    assert_eq!(q.mul(b).add(&r), *a, "q * b + r != a for {:?} / {:?}", a.as_limbs(), b.as_limbs());
+}
+
+#[test]
+fn test_div_knuth_add_back_cases() {
+    // divisors that make the two-limb quotient estimate too large
+    let cases = vec![
+        (vec![0, 0xfffe, 0x8000], vec![0xffff, 0x8000]),
+        (vec![0, 0, 0x8000_0000, 0x7fff_ffff], vec![1, 0, 0x8000_0000]),
+        (vec![3, 0, 0x8000, 0], vec![1, 0, 0x2000]),
+        (vec![0, 0, 0x8000, 0x7fff], vec![1, 0, 0x8000]),
+        (vec![0, 0xfffe, 0, 0x8000], vec![0xffff, 0, 0x8000]),
+        (vec![0, 0xffff_fffe, 0, 0x8000_0000], vec![0xffff_ffff, 0, 0x8000_0000]),
+        (vec![0x89ab_cdef, 0x0123_4567, 0, 0], vec![0x0123_4567, 0x89ab_cdef]),
+    ];
+
+    for (a, b) in cases {
+        assert_div_rem(&BigUInt::from_limbs_le(a), &BigUInt::from_limbs_le(b));
+    }
+}
+
+#[test]
+fn test_div_knuth_random() {
+    let sizes = [(2, 2), (5, 2), (10, 3), (40, 17), (64, 63), (200, 100), (300, 7)];
+
+    for (seed, &(n, m)) in sizes.iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 900));
+        let mut divisor = pseudo_random_limbs(m, seed as u64 + 901);
+
+        // small top limbs force a large normalization shift
+        *divisor.last_mut().unwrap() >>= (seed * 5) as u32 % 31;
+        let b = BigUInt::from_limbs_le(divisor);
+
+        assert_div_rem(&a, &b);
+    }
+}
+
+#[test]
+fn test_div_exact_product_round_trip() {
+    let a = BigUInt::from_limbs_le(pseudo_random_limbs(120, 41));
+    let b = BigUInt::from_limbs_le(pseudo_random_limbs(50, 42));
+    let (q, r) = a.mul(&b).div_rem(&b);
+
+    assert_eq!(q, a);
+    assert!(r.is_zero());
+}
+
+#[test]
+fn test_div_burnikel_ziegler_random() {
+    let sizes = [(200, 80), (400, 150), (1000, 333), (1500, 700), (2100, 1024), (800, 81)];
+
+    for (seed, &(n, m)) in sizes.iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 1200));
+        let mut divisor = pseudo_random_limbs(m, seed as u64 + 1201);
+        *divisor.last_mut().unwrap() >>= (seed * 7) as u32 % 31;
+        let b = BigUInt::from_limbs_le(divisor);
+
+        assert_div_rem(&a, &b);
+    }
+}
+
+#[test]
+fn test_div_burnikel_ziegler_saturated_quotient() {
+    // all-ones dividends hit the q = B^h - 1 branch of the 3n/2n step
+    let a = BigUInt::from_limbs_le(vec![u32::MAX; 900]);
+    let b = BigUInt::from_limbs_le(vec![u32::MAX; 300]);
+    let c = BigUInt::from_limbs_le([vec![0; 299], vec![1 << 31]].concat());
+
+    assert_div_rem(&a, &b);
+    assert_div_rem(&a, &c);
+    assert_div_rem(&a.mul(&b), &b);
+}
+
+fn reference_to_string(a: &BigUInt) -> String {
+    let mut digits = Vec::new();
+    let mut temp = a.clone();
+
+    while !temp.is_zero() {
+        let (q, r) = temp.div_single(10);
+        digits.push(std::char::from_digit(r, 10).unwrap());
+        temp = q;
+    }
+
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().collect()
+}
+
+#[test]
+fn test_display_matches_digit_by_digit() {
+    for (seed, n) in [1usize, 2, 3, 63, 64, 65, 130, 300].into_iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 1500));
+        assert_eq!(a.to_string(), reference_to_string(&a), "Mismatch for {} limbs", n);
+    }
+}
+
+#[test]
+fn test_display_inner_zero_chunks() {
+    // 10^k has long runs of zero chunks that must be padded, not dropped
+    for k in [9usize, 10, 576, 577, 1000, 2500] {
+        let s = format!("1{}", "0".repeat(k));
+        let a = BigUInt::from_str(&s).unwrap();
+        assert_eq!(a.to_string(), s, "Mismatch for 10^{}", k);
+
+        let s = format!("{}1", "0".repeat(k));
+        assert_eq!(BigUInt::from_str(&s).unwrap().as_limbs(), vec![1], "Mismatch for leading zeros {}", k);
+    }
+}
+
+#[test]
+fn test_from_str_large_round_trip() {
+    let mut s = String::new();
+    for i in 0..5000u32 {
+        s.push(char::from_digit((i * 7 + i / 13) % 10, 10).unwrap());
+    }
+    let s = s.trim_start_matches('0').to_string();
+
+    let a = BigUInt::from_str(&s).unwrap();
+    assert_eq!(a.to_string(), s);
+    assert_eq!(reference_to_string(&a), s);
+
+    let nines = "9".repeat(3000);
+    let b = BigUInt::from_str(&nines).unwrap();
+    assert_eq!(b.add(&BigUInt::from_u32(1)).to_string(), format!("1{}", "0".repeat(3000)));
+}
+
+#[test]
+fn test_from_str_rejects_non_ascii_digits() {
+    assert!(BigUInt::from_str("١٢٣").is_err());
+    assert!(BigUInt::from_str("12 3").is_err());
+    assert!(BigUInt::from_str("+123").is_err());
+}
+
+#[test]
+fn test_operators_all_reference_combinations() {
+    let a = BigUInt::from_str("123456789012345678901234567890").unwrap();
+    let b = BigUInt::from_str("987654321").unwrap();
+
+    let sum = a.add(&b);
+    assert_eq!(&a + &b, sum);
+    assert_eq!(a.clone() + &b, sum);
+    assert_eq!(&a + b.clone(), sum);
+    assert_eq!(a.clone() + b.clone(), sum);
+
+    let (q, r) = a.div_rem(&b);
+    assert_eq!(&a - &b, a.sub(&b));
+    assert_eq!(&a * &b, a.mul(&b));
+    assert_eq!(&a / &b, q);
+    assert_eq!(&a % &b, r);
+    assert_eq!((&a / &b) * &b + (&a % &b), a);
+}
+
+#[test]
+fn test_operators_assign() {
+    let a = BigUInt::from_str("18446744073709551616").unwrap();
+    let b = BigUInt::from_u32(3);
+
+    let mut c = a.clone();
+    c += &b;
+    c -= b.clone();
+    assert_eq!(c, a);
+
+    c *= &b;
+    assert_eq!(c.to_string(), "55340232221128654848");
+    c /= b.clone();
+    assert_eq!(c, a);
+    c %= BigUInt::from_u32(1000);
+    assert_eq!(c.to_string(), "616");
+}
+
+#[test]
+fn test_operators_with_primitives() {
+    let a = BigUInt::from_u32(1000);
+
+    assert_eq!((&a + 5u8).to_string(), "1005");
+    assert_eq!((a.clone() - 1u16).to_string(), "999");
+    assert_eq!((&a * u64::MAX).to_string(), "18446744073709551615000");
+    assert_eq!((&a / 7usize).to_string(), "142");
+    assert_eq!((&a % 7u32).to_string(), "6");
+    assert_eq!((u128::MAX + &a).to_string(), "340282366920938463463374607431768212455");
+    assert_eq!((5000u32 - a.clone()).to_string(), "4000");
+
+    let mut b = a.clone();
+    b *= 1u64 << 40;
+    b += 1u8;
+    assert_eq!(b.to_string(), "1099511627776001");
+}
+
+#[test]
+#[should_panic(expected = "Subtraction underflow")]
+fn test_operator_sub_underflow() {
+    let _ = BigUInt::from_u32(1) - 2u32;
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn test_operator_div_by_zero() {
+    let _ = BigUInt::from_u32(1) / 0u64;
+}
+
+#[test]
+fn test_in_place_arithmetic() {
+    let mut a = BigUInt::from_u32(u32::MAX);
+    a.add_assign(&BigUInt::from_u32(1));
+    assert_eq!(a.as_limbs(), vec![0, 1]);
+
+    a.sub_assign(&BigUInt::from_u32(1));
+    assert_eq!(a.as_limbs(), vec![u32::MAX]);
+
+    a.mul_single_assign(u32::MAX);
+    assert_eq!(a.as_limbs(), vec![1, u32::MAX - 1]);
+
+    let r = a.div_rem_single_assign(u32::MAX);
+    assert_eq!(a.as_limbs(), vec![u32::MAX]);
+    assert_eq!(r, 0);
 
+    a.shl_limbs_assign(2);
+    assert_eq!(a.as_limbs(), vec![0, 0, u32::MAX]);
+
+    let mut zero = BigUInt::new();
+    zero.shl_limbs_assign(3);
+    assert_eq!(zero.as_limbs(), vec![0]);
+
+    let mut padded = BigUInt::from_limbs_le_unchecked(vec![7, 0, 0]);
+    padded.truncate_assign();
+    assert_eq!(padded.as_limbs(), vec![7]);
+}
+
+#[test]
+fn test_in_place_arithmetic_reuses_buffer() {
+    let mut limbs = Vec::with_capacity(64);
+    limbs.push(1);
+    let mut a = BigUInt::from_limbs_le(limbs);
+    let buffer = a.as_limbs().as_ptr();
+
+    for _ in 0..40 {
+        a.mul_single_assign(1_000_000_000);
+        a.add_assign(&BigUInt::from_u32(123_456_789));
+    }
+    for _ in 0..40 {
+        a.div_rem_single_assign(1_000_000_000);
+        a.sub_assign(&BigUInt::from_u32(1));
+    }
+
+    assert_eq!(a.as_limbs().as_ptr(), buffer);
+}
+
+#[test]
+#[should_panic(expected = "Subtraction underflow")]
+fn test_sub_assign_underflow() {
+    let mut a = BigUInt::from_u32(1);
+    a.sub_assign(&BigUInt::from_u32(2));
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn test_div_rem_single_assign_by_zero() {
+    let mut a = BigUInt::from_u32(1);
+    a.div_rem_single_assign(0);
+}
+
+#[test]
+fn test_from_primitives() {
+    assert_eq!(BigUInt::from(200u8).as_limbs(), vec![200]);
+    assert_eq!(BigUInt::from(u16::MAX).as_limbs(), vec![65535]);
+    assert_eq!(BigUInt::from(0u64).as_limbs(), vec![0]);
+    assert_eq!(BigUInt::from(u64::MAX).as_limbs(), vec![u32::MAX, u32::MAX]);
+    assert_eq!(BigUInt::from(1u128 << 64).as_limbs(), vec![0, 0, 1]);
+    assert_eq!(BigUInt::from(u128::MAX).to_string(), u128::MAX.to_string());
+    assert_eq!(BigUInt::from(usize::MAX).to_string(), usize::MAX.to_string());
+}
+
+#[test]
+fn test_try_from_big_uint() {
+    use big_numbers::big_uint::TryFromBigIntError;
+
+    let small = BigUInt::from(300u32);
+    assert_eq!(u16::try_from(&small), Ok(300));
+    assert_eq!(u8::try_from(&small), Err(TryFromBigIntError::Overflow));
+    assert_eq!(i16::try_from(&small), Ok(300));
+    assert_eq!(i8::try_from(&small), Err(TryFromBigIntError::Overflow));
+
+    let max = BigUInt::from(u128::MAX);
+    assert_eq!(u128::try_from(&max), Ok(u128::MAX));
+    assert_eq!(u64::try_from(&max), Err(TryFromBigIntError::Overflow));
+    assert_eq!(i128::try_from(&max), Err(TryFromBigIntError::Overflow));
+    assert_eq!(i128::try_from(&BigUInt::from(i128::MAX as u128)), Ok(i128::MAX));
+
+    let huge = BigUInt::from_limbs_le(vec![0, 0, 0, 0, 1]);
+    assert_eq!(u128::try_from(&huge), Err(TryFromBigIntError::Overflow));
+
+    let padded = BigUInt::from_limbs_le_unchecked(vec![5, 0, 0, 0, 0, 0]);
+    assert_eq!(u8::try_from(&padded), Ok(5));
+    assert_eq!(usize::try_from(&BigUInt::from(usize::MAX)), Ok(usize::MAX));
+}
+
+#[test]
+fn test_padded_values_are_equal_and_hash_alike() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeMap, HashMap};
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of(value: &BigUInt) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let canonical = BigUInt::from(5u32);
+    let padded = BigUInt::from_limbs_le_unchecked(vec![5, 0, 0]);
+    assert_eq!(canonical, padded);
+    assert_eq!(canonical.cmp(&padded), Ordering::Equal);
+    assert_eq!(hash_of(&canonical), hash_of(&padded));
+
+    let empty_zero = BigUInt::from_limbs_le_unchecked(vec![]);
+    assert_eq!(empty_zero, BigUInt::new());
+    assert_eq!(hash_of(&empty_zero), hash_of(&BigUInt::from_limbs_le_unchecked(vec![0, 0])));
+
+    let mut hashed = HashMap::new();
+    hashed.insert(canonical.clone(), "five");
+    assert_eq!(hashed.get(&padded), Some(&"five"));
+
+    let mut ordered = BTreeMap::new();
+    ordered.insert(padded.clone(), "five");
+    assert_eq!(ordered.get(&canonical), Some(&"five"));
+}
+
+#[test]
+fn test_results_are_canonical() {
+    let padded = BigUInt::from_limbs_le_unchecked(vec![3, 0, 0]);
+    let a = BigUInt::from_str("123456789012345678901234567890").unwrap();
+
+    assert_eq!(padded.shift_limbs(1).as_limbs(), vec![0, 3]);
+    assert_eq!(a.sub(&a).as_limbs(), vec![0]);
+    assert_eq!(padded.mul(&padded).as_limbs(), vec![9]);
+    assert_eq!(padded.add(&BigUInt::new()).as_limbs(), vec![3]);
+    assert_eq!(a.div_rem(&a).0.as_limbs(), vec![1]);
+    assert_eq!(a.div_rem(&a).1.as_limbs(), vec![0]);
+
+    let mut empty = BigUInt::from_limbs_le_unchecked(vec![]);
+    empty.truncate_assign();
+    assert_eq!(empty.as_limbs(), vec![0]);
+}
+
+#[test]
+fn test_limb_constructors() {
+    let a = BigUInt::from_limbs_le(vec![1, 2, 0, 0]);
+    assert_eq!(a.as_limbs(), vec![1, 2]);
+    assert_eq!(a.clone().into_limbs(), vec![1, 2]);
+    assert_eq!(a.to_string(), "8589934593");
+
+    assert_eq!(BigUInt::from_limbs_le(vec![]).as_limbs(), vec![0]);
+    assert_eq!(BigUInt::from_limbs_le(vec![0, 0]).as_limbs(), vec![0]);
+
+    let raw = BigUInt::from_limbs_le_unchecked(vec![1, 2, 0, 0]);
+    assert_eq!(raw.as_limbs(), vec![1, 2, 0, 0]);
+    assert_eq!(raw, a);
+    assert_eq!(raw.truncate().as_limbs(), vec![1, 2]);
+}
+
+#[test]
+fn test_bit_shifts() {
+    let a = BigUInt::from(0x1234_5678_9abc_def0_u64);
+
+    for bits in [0usize, 1, 4, 31, 32, 33, 63, 64] {
+        assert_eq!(u128::try_from(&(&a << bits)), Ok(0x1234_5678_9abc_def0_u128 << bits), "<< {}", bits);
+        assert_eq!(u64::try_from(&(&a >> bits)), Ok(0x1234_5678_9abc_def0_u64.checked_shr(bits as u32).unwrap_or(0)), ">> {}", bits);
+    }
+
+    assert_eq!((&a >> 64).as_limbs(), vec![0]);
+    assert_eq!((&a >> 1000).as_limbs(), vec![0]);
+    assert_eq!((BigUInt::new() << 100).as_limbs(), vec![0]);
+    assert_eq!((BigUInt::from_u32(1) << 100).to_string(), "1267650600228229401496703205376");
+    assert_eq!(BigUInt::from_limbs_le_unchecked(vec![1, 0, 0]).shl(32).as_limbs(), vec![0, 1]);
+
+    let mut b = a.clone();
+    b <<= 77;
+    b >>= 77;
+    assert_eq!(b, a);
+}
+
+#[test]
+fn test_bit_shift_round_trip_random() {
+    for (seed, n) in [1usize, 2, 7, 40, 300].into_iter().enumerate() {
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed as u64 + 1200));
+
+        for bits in [1usize, 17, 32, 95, 1000] {
+            let shifted = &a << bits;
+            assert_eq!(shifted, a.mul(&BigUInt::from_u32(1).shl(bits)), "{} limbs << {}", n, bits);
+            assert_eq!(&shifted >> bits, a, "{} limbs round trip by {}", n, bits);
+            assert_eq!(&a >> bits, a.div_rem(&BigUInt::from_u32(1).shl(bits)).0, "{} limbs >> {}", n, bits);
+        }
+    }
+}
+
+#[test]
+fn test_bitwise_ops() {
+    let values = [0u128, 1, 0xff, 0xffff_ffff, 0x1_0000_0000, 0xdead_beef_0123_4567_89ab_cdef, u128::MAX];
+
+    for &x in &values {
+        for &y in &values {
+            let (a, b) = (BigUInt::from(x), BigUInt::from(y));
+            assert_eq!(u128::try_from(&(&a & &b)), Ok(x & y), "{:#x} & {:#x}", x, y);
+            assert_eq!(u128::try_from(&(&a | &b)), Ok(x | y), "{:#x} | {:#x}", x, y);
+            assert_eq!(u128::try_from(&(&a ^ &b)), Ok(x ^ y), "{:#x} ^ {:#x}", x, y);
+        }
+    }
+
+    let a = BigUInt::from_limbs_le(vec![u32::MAX; 5]);
+    let b = BigUInt::from_limbs_le(vec![0, 1]);
+    assert_eq!((&a & &b).as_limbs(), vec![0, 1]);
+    assert_eq!((&a ^ &a).as_limbs(), vec![0]);
+    assert_eq!((&b | &a).as_limbs(), vec![u32::MAX; 5]);
+
+    let mut c = a.clone();
+    c &= BigUInt::from_limbs_le_unchecked(vec![7, 0, 0]);
+    assert_eq!(c.as_limbs(), vec![7]);
+    c |= &b;
+    c ^= BigUInt::from_u32(5);
+    assert_eq!(c.as_limbs(), vec![2, 1]);
+}
+
+#[test]
+fn test_bit_inspection_matches_u128() {
+    let values = [0u128, 1, 2, 3, 0x80, 0xffff_ffff, 0x1_0000_0000, 0xdead_beef_0123_4567_89ab_cdef, 1 << 127, u128::MAX];
+
+    for &x in &values {
+        let a = BigUInt::from(x);
+        let bits = 128 - x.leading_zeros() as usize;
+
+        assert_eq!(a.bits(), bits, "bits of {:#x}", x);
+        assert_eq!(a.count_ones(), x.count_ones() as usize, "count_ones of {:#x}", x);
+        assert_eq!(a.count_zeros(), bits - x.count_ones() as usize, "count_zeros of {:#x}", x);
+        assert_eq!(a.trailing_zeros(), (x != 0).then_some(x.trailing_zeros() as usize), "trailing_zeros of {:#x}", x);
+        assert_eq!(a.is_power_of_two(), x.is_power_of_two(), "is_power_of_two of {:#x}", x);
+        assert_eq!(a.iter_ones().collect::<Vec<_>>(), (0..128).filter(|&i| x >> i & 1 == 1).collect::<Vec<_>>());
+
+        for i in [0usize, 1, 31, 32, 63, 64, 127, 128, 500] {
+            assert_eq!(a.bit(i), i < 128 && x >> i & 1 == 1, "bit {} of {:#x}", i, x);
+        }
+
+        if let Some(next) = x.checked_next_power_of_two() {
+            assert_eq!(a.next_power_of_two(), BigUInt::from(next), "next_power_of_two of {:#x}", x);
+        }
+    }
+
+    assert_eq!(BigUInt::from(u128::MAX).next_power_of_two(), BigUInt::from_u32(1) << 128);
+    assert_eq!(BigUInt::from_u32(1).leading_zeros(), 31);
+    assert_eq!(BigUInt::from(1u64 << 40).leading_zeros(), 23);
+    assert_eq!(BigUInt::new().leading_zeros(), 32);
+    assert_eq!(BigUInt::from_limbs_le_unchecked(vec![1, 0]).leading_zeros(), 31);
+}
+
+#[test]
+fn test_set_bit() {
+    let mut a = BigUInt::new();
+    a.set_bit(100, true);
+    assert_eq!(a, BigUInt::from_u32(1) << 100);
+    a.set_bit(3, true);
+    a.set_bit(3, true);
+    assert_eq!(a.iter_ones().collect::<Vec<_>>(), vec![3, 100]);
+
+    a.set_bit(100, false);
+    assert_eq!(a.as_limbs(), vec![8]);
+    a.set_bit(500, false);
+    assert_eq!(a.as_limbs(), vec![8]);
+    a.set_bit(3, false);
+    assert_eq!(a.as_limbs(), vec![0]);
+}
+
+#[test]
+fn test_pow_matches_repeated_multiplication() {
+    for base in [0u32, 1, 2, 3, 10, 12, 0xffff_ffff] {
+        let b = BigUInt::from_u32(base);
+        let mut expected = BigUInt::from_u32(1);
+
+        for exp in 0..70 {
+            assert_eq!(b.pow(exp), expected, "{}^{}", base, exp);
+            assert_eq!(b.pow_big(&BigUInt::from_u32(exp)), expected, "{}^{} with a big exponent", base, exp);
+            expected = expected.mul(&b);
+        }
+    }
+
+    assert_eq!(BigUInt::from_u32(10).pow(30).to_string(), format!("1{}", "0".repeat(30)));
+    assert_eq!(BigUInt::from_u32(7).pow(19), BigUInt::from(7u128.pow(19)));
+
+    let a = BigUInt::from_limbs_le(pseudo_random_limbs(20, 1300));
+    assert_eq!(a.pow(13), (0..12).fold(a.clone(), |acc, _| acc.mul(&a)));
+    assert_eq!(a.pow(2), a.square());
+}
+
+#[test]
+fn test_pow_of_two_is_a_shift() {
+    assert_eq!(BigUInt::from_u32(2).pow(1000), BigUInt::from_u32(1) << 1000);
+    assert_eq!(BigUInt::from_u32(8).pow(333), BigUInt::from_u32(1) << 999);
+    assert_eq!(BigUInt::from_u32(12).pow(5).to_string(), "248832");
+
+    let exp = BigUInt::from_u32(1) << 20;
+    assert_eq!(BigUInt::from_u32(2).pow_big(&exp), BigUInt::from_u32(1) << (1 << 20));
+}
+
+#[test]
+fn test_pow_size_guard() {
+    use big_numbers::big_uint::ArithmeticError;
+
+    let huge_exp = BigUInt::from_u32(1) << 70;
+    assert_eq!(BigUInt::from_u32(3).try_pow_big(&huge_exp), Err(ArithmeticError::ResourceLimit));
+    assert_eq!(BigUInt::from_u32(2).try_pow_big(&(BigUInt::from_u32(1) << 40)), Err(ArithmeticError::ResourceLimit));
+    assert_eq!(BigUInt::from(u64::MAX).try_pow(u32::MAX), Err(ArithmeticError::ResourceLimit));
+
+    assert_eq!(BigUInt::from_u32(1).try_pow_big(&huge_exp), Ok(BigUInt::from_u32(1)));
+    assert_eq!(BigUInt::new().try_pow_big(&huge_exp), Ok(BigUInt::new()));
+    assert_eq!(BigUInt::from_u32(12345).try_pow_big(&BigUInt::new()), Ok(BigUInt::from_u32(1)));
+
+    assert_eq!(BigUInt::from_u32(3).checked_pow_big(&huge_exp), None);
+    assert_eq!(BigUInt::from(u64::MAX).checked_pow(u32::MAX), None);
+    assert_eq!(BigUInt::from_u32(3).checked_pow(4), Some(BigUInt::from_u32(81)));
+    assert_eq!(BigUInt::from_u32(3).checked_pow_big(&BigUInt::from_u32(4)), Some(BigUInt::from_u32(81)));
+}
+
+#[test]
+#[should_panic(expected = "Result too large to allocate")]
+fn test_pow_too_large_panics() {
+    BigUInt::from(u64::MAX).pow(u32::MAX);
+}
+
+fn reference_modpow(base: &BigUInt, exp: &BigUInt, modulus: &BigUInt) -> BigUInt {
+    let mut result = BigUInt::from_u32(1).div_rem(modulus).1;
+    let base = base.div_rem(modulus).1;
+
+    for i in (0..exp.bits()).rev() {
+        result = result.square().div_rem(modulus).1;
+        if exp.bit(i) {
+            result = result.mul(&base).div_rem(modulus).1;
+        }
+    }
+    result
+}
+
+#[test]
+fn test_modpow_small() {
+    for base in [0u32, 1, 2, 3, 7, 100, u32::MAX] {
+        for exp in [0u32, 1, 2, 5, 16, 31, 100] {
+            for modulus in [1u32, 2, 3, 10, 97, 1 << 20, 4_294_967_291, u32::MAX] {
+                let expected = (0..exp).fold(1u128 % modulus as u128, |acc, _| acc * base as u128 % modulus as u128);
+                let result = BigUInt::from_u32(base).modpow(&BigUInt::from_u32(exp), &BigUInt::from_u32(modulus));
+                assert_eq!(result, BigUInt::from(expected), "{}^{} mod {}", base, exp, modulus);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_modpow_matches_reference() {
+    for (seed, &(n, e)) in [(1usize, 3usize), (2, 2), (5, 9), (16, 16), (33, 40), (64, 10)].iter().enumerate() {
+        let seed = seed as u64 * 10 + 1400;
+        let mut odd = pseudo_random_limbs(n, seed);
+        odd[0] |= 1;
+        let mut even = pseudo_random_limbs(n, seed + 1);
+        even[0] &= !7;
+
+        let base = BigUInt::from_limbs_le(pseudo_random_limbs(n + 3, seed + 2));
+        let exp = BigUInt::from_limbs_le(pseudo_random_limbs(e, seed + 3));
+
+        for modulus in [BigUInt::from_limbs_le(odd), BigUInt::from_limbs_le(even)] {
+            assert_eq!(base.modpow(&exp, &modulus), reference_modpow(&base, &exp, &modulus), "{} limb modulus, {} limb exponent", n, e);
+        }
+    }
+}
+
+#[test]
+fn test_modpow_fermat() {
+    // 2^521 - 1 and 2^607 - 1 are Mersenne primes
+    for p_bits in [521, 607] {
+        let p = (BigUInt::from_u32(1) << p_bits).sub(&BigUInt::from_u32(1));
+        let p_minus_one = p.sub(&BigUInt::from_u32(1));
+
+        for a in [2u32, 3, 12345, u32::MAX] {
+            assert_eq!(BigUInt::from_u32(a).modpow(&p_minus_one, &p), BigUInt::from_u32(1), "{}^(p-1) mod 2^{}-1", a, p_bits);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn test_modpow_zero_modulus() {
+    BigUInt::from_u32(2).modpow(&BigUInt::from_u32(3), &BigUInt::new());
+}
+
+fn reference_gcd(a: &BigUInt, b: &BigUInt) -> BigUInt {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while !b.is_zero() {
+        let r = a.div_rem(&b).1;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+fn assert_extended_gcd(a: &BigUInt, b: &BigUInt) {
+    use big_numbers::big_int::BigInt;
+
+    let (g, x, y) = a.extended_gcd(b);
+    assert_eq!(g, reference_gcd(a, b), "gcd of {} and {}", a, b);
+
+    let signed = |v: &BigUInt| BigInt::from_str(&v.to_string()).unwrap();
+    assert_eq!(signed(a).mul(&x).add(&signed(b).mul(&y)), signed(&g), "Bezout identity for {} and {}", a, b);
+
+    if !g.is_zero() {
+        assert!(*x.magnitude() <= b.div_rem(&g).0.max(BigUInt::from_u32(1)), "x too large for {} and {}", a, b);
+        assert!(*y.magnitude() <= a.div_rem(&g).0.max(BigUInt::from_u32(1)), "y too large for {} and {}", a, b);
+    }
+}
+
+#[test]
+fn test_gcd_small() {
+    let values = [0u128, 1, 2, 6, 12, 35, 97, 1 << 40, 3 * (1 << 40), u64::MAX as u128, u128::MAX, 340282366920938463463374607431768211297];
+
+    for &x in &values {
+        for &y in &values {
+            let (a, b) = (BigUInt::from(x), BigUInt::from(y));
+            let g = reference_gcd(&a, &b);
+            assert_eq!(a.gcd(&b), g, "gcd({}, {})", x, y);
+
+            let lcm = if x == 0 || y == 0 { BigUInt::new() } else { a.mul(&b).div_rem(&g).0 };
+            assert_eq!(a.lcm(&b), lcm, "lcm({}, {})", x, y);
+            assert_extended_gcd(&a, &b);
+        }
+    }
+}
+
+#[test]
+fn test_gcd_large() {
+    for (seed, &(n, m, k)) in [(6usize, 6usize, 1usize), (10, 5, 3), (40, 40, 7), (64, 30, 20), (150, 149, 50)].iter().enumerate() {
+        let seed = seed as u64 * 10 + 1500;
+        let common = BigUInt::from_limbs_le(pseudo_random_limbs(k, seed)).shl(seed as usize % 70);
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(n, seed + 1)).mul(&common);
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(m, seed + 2)).mul(&common);
+
+        let g = a.gcd(&b);
+        assert_eq!(g, reference_gcd(&a, &b), "{}x{} limbs", n, m);
+        assert!(g >= common);
+        assert_eq!(a.lcm(&b), a.div_rem(&g).0.mul(&b));
+        assert_extended_gcd(&a, &b);
+        assert_extended_gcd(&b, &a);
+    }
+}
+
+#[test]
+fn test_gcd_fibonacci() {
+    // consecutive Fibonacci numbers take the longest quotient sequence
+    let (mut a, mut b) = (BigUInt::from_u32(1), BigUInt::from_u32(1));
+    for _ in 0..3000 {
+        let next = a.add(&b);
+        a = b;
+        b = next;
+    }
+
+    assert_eq!(a.gcd(&b), BigUInt::from_u32(1));
+    assert_extended_gcd(&b, &a);
+}
+
+#[test]
+fn test_mod_inverse() {
+    let m = BigUInt::from_u32(97);
+    for a in 1..97u32 {
+        let inv = BigUInt::from_u32(a).mod_inverse(&m).unwrap();
+        assert!(inv < m);
+        assert_eq!(BigUInt::from_u32(a).mod_mul(&inv, &m), BigUInt::from_u32(1), "inverse of {} mod 97", a);
+    }
+
+    assert_eq!(BigUInt::from_u32(6).mod_inverse(&BigUInt::from_u32(9)), None);
+    assert_eq!(BigUInt::new().mod_inverse(&m), None);
+    assert_eq!(BigUInt::from_u32(97 * 5 + 3).mod_inverse(&m), BigUInt::from_u32(3).mod_inverse(&m));
+    assert_eq!(BigUInt::from_u32(5).mod_inverse(&BigUInt::from_u32(1)), Some(BigUInt::new()));
+
+    let p = (BigUInt::from_u32(1) << 521).sub(&BigUInt::from_u32(1));
+    let a = BigUInt::from_limbs_le(pseudo_random_limbs(30, 1600));
+    let inv = a.mod_inverse(&p).unwrap();
+    assert_eq!(a.mod_mul(&inv, &p), BigUInt::from_u32(1));
+    assert_eq!(inv, a.modpow(&p.sub(&BigUInt::from_u32(2)), &p));
+}
+
+#[test]
+fn test_mod_helpers_stay_in_range() {
+    let m = BigUInt::from_u32(1000);
+    let cases = [(0u32, 0u32), (999, 1), (999, 999), (123, 456), (456, 123), (5000, 7), (7, 5000), (1_000_000, 999_999)];
+
+    for (x, y) in cases {
+        let (a, b) = (BigUInt::from_u32(x), BigUInt::from_u32(y));
+        let (x, y) = (x as i64, y as i64);
+
+        assert_eq!(a.mod_add(&b, &m), BigUInt::from(((x + y) % 1000) as u32), "{} + {}", x, y);
+        assert_eq!(a.mod_sub(&b, &m), BigUInt::from((x - y).rem_euclid(1000) as u32), "{} - {}", x, y);
+        assert_eq!(a.mod_mul(&b, &m), BigUInt::from((x * y % 1000) as u32), "{} * {}", x, y);
+        assert_eq!(a.mod_neg(&m), BigUInt::from((-x).rem_euclid(1000) as u32), "-{}", x);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn test_mod_helpers_zero_modulus() {
+    BigUInt::from_u32(1).mod_add(&BigUInt::from_u32(2), &BigUInt::new());
+}
+
+#[test]
+fn test_checked_and_try_arithmetic() {
+    use big_numbers::big_uint::ArithmeticError;
+
+    let small = BigUInt::from_u32(5);
+    let big = BigUInt::from_str("123456789012345678901234567890").unwrap();
+    let zero = BigUInt::new();
+
+    assert_eq!(big.checked_sub(&small), Some(big.sub(&small)));
+    assert_eq!(small.checked_sub(&big), None);
+    assert_eq!(small.try_sub(&big), Err(ArithmeticError::Underflow));
+    assert_eq!(small.try_sub(&small), Ok(zero.clone()));
+
+    assert_eq!(small.saturating_sub(&big), zero);
+    assert_eq!(big.saturating_sub(&small), big.sub(&small));
+    assert_eq!(small.overflowing_sub(&big), (big.sub(&small), true));
+    assert_eq!(big.overflowing_sub(&small), (big.sub(&small), false));
+
+    assert_eq!(big.checked_div(&small), Some(big.div_rem(&small).0));
+    assert_eq!(big.checked_rem(&small), Some(big.div_rem(&small).1));
+    assert_eq!(big.checked_div(&zero), None);
+    assert_eq!(big.checked_rem(&zero), None);
+    assert_eq!(big.try_div(&small), Ok(big.div_rem(&small)));
+    assert_eq!(big.try_div(&zero), Err(ArithmeticError::DivisionByZero));
+    assert_eq!(big.try_rem(&zero), Err(ArithmeticError::DivisionByZero));
+
+    assert_eq!(big.checked_div_single(7), Some(big.div_single(7)));
+    assert_eq!(big.checked_div_single(0), None);
+    assert_eq!(big.try_div_single(0), Err(ArithmeticError::DivisionByZero));
+
+    assert_eq!(ArithmeticError::Underflow.to_string(), "Subtraction underflow");
+    assert_eq!(ArithmeticError::DivisionByZero.to_string(), "Division by zero");
+}
+
+#[test]
+fn test_big_int_checked_and_try_division() {
+    use big_numbers::big_int::BigInt;
+    use big_numbers::big_uint::ArithmeticError;
+
+    let a = BigInt::from_str("-123456789012345678901234567890").unwrap();
+    let b = BigInt::from(-7);
+    let zero = BigInt::new();
+    let negative_zero = BigInt::from_str("-0").unwrap();
+
+    assert_eq!(a.checked_div(&b), Some(a.div_rem(&b).0));
+    assert_eq!(a.checked_rem(&b), Some(a.div_rem(&b).1));
+    assert_eq!(a.try_div(&b), Ok(a.div_rem(&b)));
+    assert_eq!(a.try_rem(&b), Ok(a.div_rem(&b).1));
+
+    // truncating like div_rem, so the remainder keeps the dividend's sign
+    assert_eq!(BigInt::from(-7).try_div(&BigInt::from(2)), Ok((BigInt::from(-3), BigInt::from(-1))));
+
+    for divisor in [&zero, &negative_zero] {
+        assert_eq!(a.checked_div(divisor), None);
+        assert_eq!(a.checked_rem(divisor), None);
+        assert_eq!(a.try_div(divisor), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(a.try_rem(divisor), Err(ArithmeticError::DivisionByZero));
+    }
+}
+
+fn u128_to_radix(mut x: u128, radix: u32) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(std::char::from_digit((x % radix as u128) as u32, radix).unwrap());
+        x /= radix as u128;
+        if x == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+#[test]
+fn test_radix_matches_u128() {
+    let values = [0u128, 1, 35, 36, 4294967295, 4294967296, 0xdead_beef_0123_4567_89ab_cdef, u64::MAX as u128 * 3, u128::MAX];
+
+    for radix in 2..=36 {
+        for &v in &values {
+            let expected = u128_to_radix(v, radix);
+            let a = BigUInt::from(v);
+
+            assert_eq!(a.to_str_radix(radix), expected, "format {} in radix {}", v, radix);
+            assert_eq!(BigUInt::from_str_radix(&expected, radix), Ok(a.clone()));
+            assert_eq!(BigUInt::from_str_radix(&expected.to_uppercase(), radix), Ok(a));
+        }
+    }
+
+    assert_eq!(BigUInt::from_str_radix("DeadBeef", 16).unwrap(), BigUInt::from(0xdeadbeefu32));
+    assert_eq!(BigUInt::from_str_radix("000101", 2).unwrap(), BigUInt::from(5u32));
+}
+
+#[test]
+fn test_radix_round_trip_large() {
+    let mut a = BigUInt::from_str("1").unwrap();
+    for i in 0..200u32 {
+        a = a.mul(&BigUInt::from(0x9e3779b97f4a7c15u64)).add(&BigUInt::from(i));
+    }
+
+    for radix in 2..=36 {
+        let s = a.to_str_radix(radix);
+        assert_eq!(BigUInt::from_str_radix(&s, radix).unwrap(), a, "radix {}", radix);
+    }
+    assert_eq!(a.to_str_radix(10), a.to_string());
+    assert_eq!(a.to_str_radix(16).len(), a.bits().div_ceil(4));
+}
+
+#[test]
+fn test_radix_zero_runs_large() {
+    // radix^n + radix^(n/2) + 1 needs the zero padding between the recursive halves
+    for radix in [3u32, 7, 10, 36] {
+        let n = 5000;
+        let r = BigUInt::from_u32(radix);
+        let a = r.pow(n).add(&r.pow(n / 2)).add(&BigUInt::from_u32(1));
+
+        let zeros = "0".repeat(n as usize / 2 - 1);
+        let expected = format!("1{}1{}1", zeros, zeros);
+        assert_eq!(a.to_str_radix(radix), expected, "radix {}", radix);
+        assert_eq!(BigUInt::from_str_radix(&expected, radix).unwrap(), a, "radix {}", radix);
+    }
+}
+
+#[test]
+fn test_radix_power_cache_limit() {
+    use big_numbers::big_uint::{clear_radix_power_cache, radix_power_cache_limit, set_radix_power_cache_limit};
+
+    let default = radix_power_cache_limit();
+    let a = BigUInt::from_limbs_le(pseudo_random_limbs(600, 11));
+
+    // with nothing cached past the first power, every conversion rebuilds the rest
+    set_radix_power_cache_limit(0);
+    for radix in [7, 10] {
+        let s = a.to_str_radix(radix);
+        assert_eq!(BigUInt::from_str_radix(&s, radix).unwrap(), a, "radix {}", radix);
+    }
+
+    clear_radix_power_cache();
+    set_radix_power_cache_limit(default);
+    for radix in [7, 10] {
+        let s = a.to_str_radix(radix);
+        assert_eq!(BigUInt::from_str_radix(&s, radix).unwrap(), a, "radix {}", radix);
+        clear_radix_power_cache();
+        assert_eq!(a.to_str_radix(radix), s, "radix {}", radix);
+    }
+}
+
+#[test]
+fn test_radix_errors() {
+    use big_numbers::big_uint::ParseErrorKind;
+
+    for radix in [0, 1, 37] {
+        assert_eq!(BigUInt::from_str_radix("1", radix).unwrap_err().kind(), ParseErrorKind::InvalidRadix);
+    }
+    assert_eq!(BigUInt::from_str_radix("", 16).unwrap_err().kind(), ParseErrorKind::Empty);
+
+    let err = BigUInt::from_str_radix("10201", 2).unwrap_err();
+    assert_eq!((err.kind(), err.position(), err.character()), (ParseErrorKind::InvalidDigit, 2, Some('2')));
+
+    let err = BigUInt::from_str_radix("0xff", 16).unwrap_err();
+    assert_eq!((err.position(), err.character()), (1, Some('x')));
+}
+
+#[test]
+#[should_panic(expected = "Radix must be between 2 and 36")]
+fn test_to_str_radix_invalid() {
+    BigUInt::from_u32(5).to_str_radix(37);
+}
+
+#[test]
+fn test_formatting_matches_primitives() {
+    for v in [0u128, 1, 15, 125, 135, 999, 1000, 1251, 4294967296, u64::MAX as u128, u128::MAX] {
+        let a = BigUInt::from(v);
+
+        assert_eq!(format!("{:x} {:#x} {:X} {:#X}", a, a, a, a), format!("{:x} {:#x} {:X} {:#X}", v, v, v, v));
+        assert_eq!(format!("{:o} {:#o} {:b} {:#b}", a, a, a, a), format!("{:o} {:#o} {:b} {:#b}", v, v, v, v));
+        assert_eq!(format!("{:#034x} {:>40b} {:<6o}", a, a, a), format!("{:#034x} {:>40b} {:<6o}", v, v, v));
+        assert_eq!(format!("[{:>45}] [{:_<8}] [{:+}] [{:05}]", a, a, a, a), format!("[{:>45}] [{:_<8}] [{:+}] [{:05}]", v, v, v, v));
+
+        for precision in 0..5 {
+            assert_eq!(format!("{:.*e}", precision, a), format!("{:.*e}", precision, v));
+        }
+        assert_eq!(format!("{:e} {:>20e}", a, a), format!("{:e} {:>20e}", v, v));
+    }
+
+    // beyond u128, checked against the decimal digits
+    let a = BigUInt::from_str("99999999999999999999999999999999999999999999999999").unwrap();
+    assert_eq!(format!("{:.3e}", a), "1.000e50");
+    assert_eq!(format!("{:e}", a.add(&BigUInt::from_u32(1))), "1e50");
+    assert_eq!(format!("{:.60e}", a), format!("9.{}{}e49", "9".repeat(49), "0".repeat(11)));
+    assert_eq!(format!("{:x}", a.shl(4)), format!("{:x}0", a));
+}
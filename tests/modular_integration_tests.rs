@@ -0,0 +1,231 @@
+use big_numbers::modular::{BarrettContext, MontgomeryContext};
+use big_numbers::BigUInt;
+
+fn pseudo_random_limbs(len: usize, seed: u64) -> Vec<u32> {
+    let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u32
+        })
+        .collect()
+}
+
+fn odd_modulus(len: usize, seed: u64) -> BigUInt {
+    let mut limbs = pseudo_random_limbs(len, seed);
+    limbs[0] |= 1;
+    *limbs.last_mut().unwrap() |= 1;
+    BigUInt::from_limbs_le(limbs)
+}
+
+fn padded(x: &BigUInt, len: usize) -> Vec<u32> {
+    let mut limbs = x.as_limbs().to_vec();
+    limbs.resize(len, 0);
+    limbs
+}
+
+#[test]
+fn test_montgomery_round_trip() {
+    for (seed, len) in [1usize, 2, 3, 8, 64].into_iter().enumerate() {
+        let n = odd_modulus(len, seed as u64 + 10);
+        let ctx = MontgomeryContext::new(&n);
+        assert_eq!(ctx.modulus(), &n);
+        assert_eq!(ctx.limbs(), len);
+
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(len + 2, seed as u64 + 20));
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 30)).div_rem(&n).1;
+        let expected = a.mul(&b).div_rem(&n).1;
+
+        let (a_mont, b_mont) = (ctx.to_mont(&a), ctx.to_mont(&b));
+        assert_eq!(ctx.from_mont(&a_mont), a.div_rem(&n).1, "{} limbs", len);
+        assert_eq!(ctx.from_mont(&ctx.mul(&a_mont, &b_mont)), expected, "{} limbs", len);
+        assert_eq!(ctx.from_mont(&ctx.square(&b_mont)), b.square().div_rem(&n).1, "{} limbs", len);
+
+        // reduce accepts values of any size
+        let big = a.mul(&a).mul(&a);
+        assert_eq!(ctx.reduce(&ctx.to_mont(&big)), big.div_rem(&n).1, "{} limbs", len);
+    }
+}
+
+#[test]
+fn test_montgomery_limbs_match_big_uint_api() {
+    for (seed, len) in [1usize, 2, 5, 32, 100].into_iter().enumerate() {
+        let n = odd_modulus(len, seed as u64 + 40);
+        let ctx = MontgomeryContext::new(&n);
+
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 50)).div_rem(&n).1;
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 60)).div_rem(&n).1;
+
+        let mut a_mont = vec![0; len];
+        let mut b_mont = vec![0; len];
+        ctx.to_mont_limbs(&padded(&a, len), &mut a_mont);
+        ctx.to_mont_limbs(&padded(&b, len), &mut b_mont);
+        assert_eq!(a_mont, padded(&ctx.to_mont(&a), len), "{} limbs", len);
+
+        let mut product = vec![0; len];
+        ctx.mul_limbs(&a_mont, &b_mont, &mut product);
+        assert_eq!(product, padded(&ctx.mul(&ctx.to_mont(&a), &ctx.to_mont(&b)), len), "{} limbs", len);
+
+        let mut squared = vec![0; len];
+        ctx.square_limbs(&product, &mut squared);
+
+        let mut out = vec![0; len];
+        ctx.from_mont_limbs(&squared, &mut out);
+        assert_eq!(BigUInt::from_limbs_le(out), a.mul(&b).square().div_rem(&n).1, "{} limbs", len);
+    }
+}
+
+#[test]
+fn test_montgomery_limbs_edge_values() {
+    // n - 1 squared stresses the final conditional subtraction
+    let n = BigUInt::from_limbs_le(vec![u32::MAX; 4]);
+    let ctx = MontgomeryContext::new(&n);
+    let n_minus_one = n.sub(&BigUInt::from_u32(1));
+
+    let mut x = vec![0; 4];
+    ctx.to_mont_limbs(&padded(&n_minus_one, 4), &mut x);
+    let mut y = vec![0; 4];
+    ctx.square_limbs(&x, &mut y);
+    ctx.from_mont_limbs(&y, &mut x);
+    assert_eq!(BigUInt::from_limbs_le(x), BigUInt::from_u32(1));
+
+    let ctx = MontgomeryContext::new(&BigUInt::from_u32(3));
+    let mut two = [0];
+    ctx.to_mont_limbs(&[2], &mut two);
+    let mut four = [0];
+    ctx.square_limbs(&two, &mut four);
+    let mut out = [0];
+    ctx.from_mont_limbs(&four, &mut out);
+    assert_eq!(out, [1]);
+}
+
+#[test]
+fn test_montgomery_reduce_limbs() {
+    for (seed, len) in [1usize, 2, 5, 32, 100].into_iter().enumerate() {
+        let n = odd_modulus(len, seed as u64 + 110);
+        let ctx = MontgomeryContext::new(&n);
+
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 120)).div_rem(&n).1;
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 130)).div_rem(&n).1;
+        let (a_mont, b_mont) = (padded(&ctx.to_mont(&a), len), padded(&ctx.to_mont(&b), len));
+
+        // REDC of a full product matches the interleaved multiplication
+        let mut t = padded(&BigUInt::from_limbs_le(a_mont.clone()).mul(&BigUInt::from_limbs_le(b_mont.clone())), 2 * len + 1);
+        let mut reduced = vec![0; len];
+        ctx.reduce_limbs(&mut t, &mut reduced);
+
+        let mut product = vec![0; len];
+        ctx.mul_limbs(&a_mont, &b_mont, &mut product);
+        assert_eq!(reduced, product, "{} limbs", len);
+
+        // and agrees with reduce for any t below n * R
+        let t_big = n.mul(&BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 140))).add(&a);
+        let mut t = padded(&t_big, 2 * len + 1);
+        ctx.reduce_limbs(&mut t, &mut reduced);
+        assert_eq!(BigUInt::from_limbs_le(reduced), ctx.reduce(&t_big), "{} limbs", len);
+    }
+
+    // (n - 1)^2 reduces to R^-1 mod n, the largest product of two residues
+    let n = BigUInt::from_limbs_le(vec![u32::MAX; 3]);
+    let ctx = MontgomeryContext::new(&n);
+    let mut t = padded(&n.sub(&BigUInt::from_u32(1)).square(), 7);
+    let mut out = vec![0; 3];
+    ctx.reduce_limbs(&mut t, &mut out);
+    assert_eq!(BigUInt::from_limbs_le(out), ctx.reduce(&n.sub(&BigUInt::from_u32(1)).square()));
+}
+
+#[test]
+#[should_panic(expected = "Reduction needs 5 input and 2 output limbs")]
+fn test_montgomery_reduce_limbs_wrong_length() {
+    let ctx = MontgomeryContext::new(&BigUInt::from_limbs_le(vec![1, 1]));
+    let mut out = [0; 2];
+    ctx.reduce_limbs(&mut [0; 4], &mut out);
+}
+
+#[test]
+#[should_panic(expected = "Montgomery modulus must be odd")]
+fn test_montgomery_even_modulus() {
+    MontgomeryContext::new(&BigUInt::from_u32(10));
+}
+
+#[test]
+#[should_panic(expected = "Residues must have exactly 2 limbs")]
+fn test_montgomery_limbs_wrong_length() {
+    let ctx = MontgomeryContext::new(&BigUInt::from_limbs_le(vec![1, 1]));
+    let mut out = [0; 2];
+    ctx.mul_limbs(&[1], &[1, 0], &mut out);
+}
+
+#[test]
+fn test_barrett_reduce() {
+    for (seed, len) in [1usize, 2, 7, 40].into_iter().enumerate() {
+        let mut limbs = pseudo_random_limbs(len, seed as u64 + 70);
+        limbs[0] &= !1;
+        *limbs.last_mut().unwrap() |= 1;
+        let n = BigUInt::from_limbs_le(limbs);
+        let ctx = BarrettContext::new(&n);
+        assert_eq!(ctx.modulus(), &n);
+
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 80)).div_rem(&n).1;
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 90)).div_rem(&n).1;
+        assert_eq!(ctx.mul(&a, &b), a.mul(&b).div_rem(&n).1, "{} limbs", len);
+        assert_eq!(ctx.square(&a), a.square().div_rem(&n).1, "{} limbs", len);
+
+        let huge = a.mul(&b).mul(&n).add(&a).mul(&b).shl(100);
+        assert_eq!(ctx.reduce(&huge), huge.div_rem(&n).1, "{} limbs", len);
+
+        let mut x = n.sub(&BigUInt::from_u32(1)).square();
+        ctx.reduce_assign(&mut x);
+        assert_eq!(x, BigUInt::from_u32(1), "{} limbs", len);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn test_barrett_zero_modulus() {
+    BarrettContext::new(&BigUInt::new());
+}
+
+#[test]
+fn test_barrett_limbs_match_big_uint_api() {
+    let mut moduli: Vec<BigUInt> = [1usize, 2, 7, 40].into_iter().enumerate()
+        .map(|(seed, len)| BigUInt::from_limbs_le(pseudo_random_limbs(len, seed as u64 + 150)))
+        .collect();
+    // 2^(32(k-1)) makes mu one limb longer than usual, all ones stresses the corrections
+    moduli.push(BigUInt::from_limbs_le(vec![0, 0, 1]));
+    moduli.push(BigUInt::from_limbs_le(vec![u32::MAX; 4]));
+    moduli.push(BigUInt::from_u32(10));
+
+    for (seed, n) in moduli.iter().enumerate() {
+        let ctx = BarrettContext::new(n);
+        let k = ctx.limbs();
+        let mut scratch = vec![0; ctx.scratch_limbs()];
+
+        let a = BigUInt::from_limbs_le(pseudo_random_limbs(k, seed as u64 + 160)).div_rem(n).1;
+        let b = BigUInt::from_limbs_le(pseudo_random_limbs(k, seed as u64 + 170)).div_rem(n).1;
+
+        let mut out = vec![0; k];
+        ctx.mul_limbs(&padded(&a, k), &padded(&b, k), &mut out, &mut scratch);
+        assert_eq!(out, padded(&ctx.mul(&a, &b), k), "{:?}", n);
+
+        ctx.square_limbs(&padded(&a, k), &mut out, &mut scratch);
+        assert_eq!(out, padded(&ctx.square(&a), k), "{:?}", n);
+
+        // any 2k limb value, not just products of residues
+        for x in [pseudo_random_limbs(2 * k, seed as u64 + 180), vec![u32::MAX; 2 * k], vec![0; 2 * k]] {
+            ctx.reduce_limbs(&x, &mut out, &mut scratch);
+            let x = BigUInt::from_limbs_le(x);
+            assert_eq!(out, padded(&x.div_rem(n).1, k), "{:?} mod {:?}", x, n);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Scratch must have at least 14 limbs")]
+fn test_barrett_limbs_short_scratch() {
+    let ctx = BarrettContext::new(&BigUInt::from_limbs_le(vec![6, 1]));
+    let mut out = [0; 2];
+    ctx.mul_limbs(&[1, 0], &[1, 0], &mut out, &mut [0; 13]);
+}